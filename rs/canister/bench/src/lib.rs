@@ -0,0 +1,7 @@
+// Benchmarks the instructions a canister bot spends verifying the tokens sent by OpenChat. Each
+// benchmark verifies the same token either against a PEM, which is parsed every time as was the
+// case before `PublicKey` was introduced, or against a key which was parsed once up front.
+
+// The benchmarks are only built by `canbench`, which enables the `canbench-rs` feature.
+#[cfg(feature = "canbench-rs")]
+mod benches;
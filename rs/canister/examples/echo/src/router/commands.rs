@@ -19,8 +19,8 @@ pub fn definitions() -> Vec<BotCommandDefinition> {
 }
 
 pub async fn execute(request: HttpRequest) -> HttpResponse {
-    let public_key = state::read(|state| state.oc_public_key().to_string());
+    let public_keys = state::read(|state| state.oc_public_keys().clone());
     let now = now();
 
-    http_command_handler::execute(request, &COMMANDS, &public_key, now).await
+    http_command_handler::execute(request, &COMMANDS, &public_keys, now).await
 }
@@ -52,6 +52,7 @@ impl Echo {
                     max_length: 1000,
                     choices: Vec::new(),
                     multi_line: true,
+                    autocomplete: false,
                 }),
                 required: true,
                 placeholder: None,
@@ -59,6 +60,7 @@ impl Echo {
             permissions: BotPermissions::text_only(),
             default_role: None,
             direct_messages: Some(true),
+            subcommands: vec![],
         }
     }
 }
@@ -1,3 +1,5 @@
+use oc_bots_sdk::TrustedKeys;
+use oc_bots_sdk_canister::trusted_keys_from_pem_or_trap;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 
@@ -7,7 +9,8 @@ thread_local! {
 
 #[derive(Serialize, Deserialize)]
 pub struct State {
-    oc_public_key: String,
+    #[serde(alias = "oc_public_key")]
+    oc_public_keys: TrustedKeys,
 }
 
 const STATE_ALREADY_INITIALIZED: &str = "State has already been initialized";
@@ -37,14 +40,16 @@ pub fn take() -> State {
 
 impl State {
     pub fn new(oc_public_key: String) -> State {
-        State { oc_public_key }
+        State {
+            oc_public_keys: trusted_keys_from_pem_or_trap(&oc_public_key),
+        }
     }
 
     pub fn update(&mut self, oc_public_key: String) {
-        self.oc_public_key = oc_public_key;
+        self.oc_public_keys = trusted_keys_from_pem_or_trap(&oc_public_key);
     }
 
-    pub fn oc_public_key(&self) -> &str {
-        &self.oc_public_key
+    pub fn oc_public_keys(&self) -> &TrustedKeys {
+        &self.oc_public_keys
     }
 }
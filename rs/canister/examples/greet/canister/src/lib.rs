@@ -6,6 +6,7 @@ use ic_stable_structures::{
     reader::{BufferedReader, Reader},
     writer::{BufferedWriter, Writer},
 };
+use oc_bots_sdk::jwt::PublicKey;
 use oc_bots_sdk_canister::env;
 use serde::{Deserialize, Serialize};
 use state::State;
@@ -82,6 +83,46 @@ fn insert_jokes(args: InsertJokesArgs) -> InsertJokesResponse {
     })
 }
 
+// Trusts an additional OpenChat public key, eg. ahead of OpenChat rotating its key
+#[update]
+fn add_oc_public_key(args: AddOcPublicKeyArgs) -> AddOcPublicKeyResponse {
+    state::mutate(|state| {
+        if *state.administrator() != env::caller() {
+            return AddOcPublicKeyResponse::NotAuthorized;
+        }
+
+        let public_key = match PublicKey::from_pem(&args.public_key) {
+            Ok(public_key) => public_key,
+            Err(error) => return AddOcPublicKeyResponse::InvalidKey(error.to_string()),
+        };
+
+        let mut oc_public_keys = state.oc_public_keys().clone();
+        oc_public_keys.add(args.kid, public_key);
+        state.set_oc_public_keys(oc_public_keys);
+        AddOcPublicKeyResponse::Success
+    })
+}
+
+// Stops trusting an OpenChat public key, eg. once OpenChat has finished rotating its key
+#[update]
+fn retire_oc_public_key(args: RetireOcPublicKeyArgs) -> RetireOcPublicKeyResponse {
+    state::mutate(|state| {
+        if *state.administrator() != env::caller() {
+            return RetireOcPublicKeyResponse::NotAuthorized;
+        }
+
+        let mut oc_public_keys = state.oc_public_keys().clone();
+        if !oc_public_keys.retire(&args.key) {
+            RetireOcPublicKeyResponse::KeyNotFound
+        } else if oc_public_keys.is_empty() {
+            RetireOcPublicKeyResponse::CannotRetireLastKey
+        } else {
+            state.set_oc_public_keys(oc_public_keys);
+            RetireOcPublicKeyResponse::Success
+        }
+    })
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub enum InitOrUpgradeArgs {
     Init(InitArgs),
@@ -110,3 +151,30 @@ pub enum InsertJokesResponse {
     Success(u32),
     NotAuthorized,
 }
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct AddOcPublicKeyArgs {
+    pub kid: Option<String>,
+    pub public_key: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub enum AddOcPublicKeyResponse {
+    Success,
+    InvalidKey(String),
+    NotAuthorized,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub struct RetireOcPublicKeyArgs {
+    // Either the key's `kid` or its PEM
+    pub key: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize)]
+pub enum RetireOcPublicKeyResponse {
+    Success,
+    KeyNotFound,
+    CannotRetireLastKey,
+    NotAuthorized,
+}
@@ -5,6 +5,7 @@ use std::sync::LazyLock;
 mod blobs;
 mod commands;
 mod definition;
+mod events;
 mod metrics;
 mod webhooks;
 
@@ -13,6 +14,7 @@ static ROUTER: LazyLock<HttpRouter> = LazyLock::new(init_router);
 fn init_router() -> HttpRouter {
     HttpRouter::default()
         .route("/execute_command", POST, commands::execute)
+        .route("/notify", POST, events::notify)
         .route("/webhook/*", POST, webhooks::execute)
         .route("/metrics", GET, metrics::get)
         .route("/blobs/*", GET, blobs::get)
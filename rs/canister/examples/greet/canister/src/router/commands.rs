@@ -3,7 +3,7 @@ use fractal::Fractal;
 use greet::Greet;
 use joke::Joke;
 use message::Message;
-use oc_bots_sdk::api::command::CommandHandlerRegistry;
+use oc_bots_sdk::api::command::{CommandHandlerRegistry, RateLimiter};
 use oc_bots_sdk::api::definition::BotCommandDefinition;
 use oc_bots_sdk_canister::env::now;
 use oc_bots_sdk_canister::http_command_handler;
@@ -23,6 +23,13 @@ static COMMANDS: LazyLock<CommandHandlerRegistry<CanisterRuntime>> = LazyLock::n
         .register(Joke)
         .register(Fractal)
         .register(Message)
+        // Generating fractals is expensive so limit how often they can be requested
+        .with_interceptor(
+            RateLimiter::new()
+                .per_user(3, 60_000)
+                .per_scope(10, 60_000)
+                .for_commands(&["fractal"]),
+        )
 });
 
 pub fn definitions() -> Vec<BotCommandDefinition> {
@@ -30,8 +37,8 @@ pub fn definitions() -> Vec<BotCommandDefinition> {
 }
 
 pub async fn execute(request: HttpRequest) -> HttpResponse {
-    let public_key = state::read(|state| state.oc_public_key().to_string());
+    let public_keys = state::read(|state| state.oc_public_keys().clone());
     let now = now();
 
-    http_command_handler::execute(request, &COMMANDS, &public_key, now).await
+    http_command_handler::execute(request, &COMMANDS, &public_keys, now).await
 }
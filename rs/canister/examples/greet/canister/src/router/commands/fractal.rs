@@ -1,7 +1,7 @@
 use crate::state;
 use crate::state::Blob;
 use async_trait::async_trait;
-use oc_bots_sdk::api::command::{CommandHandler, SuccessResult};
+use oc_bots_sdk::api::command::{CommandArgs, CommandHandler, SuccessResult};
 use oc_bots_sdk::api::definition::*;
 use oc_bots_sdk::create_thumbnail;
 use oc_bots_sdk::oc_api::actions::send_message;
@@ -16,6 +16,24 @@ static DEFINITION: LazyLock<BotCommandDefinition> = LazyLock::new(Fractal::defin
 
 pub struct Fractal;
 
+#[derive(CommandArgs)]
+struct Args {
+    #[arg(
+        description = "The real part of the complex number input",
+        placeholder = "Enter the real part e.g. -0.4",
+        min_value = -1.0,
+        max_value = 1.0
+    )]
+    real: f32,
+    #[arg(
+        description = "The imaginary part of the complex number input",
+        placeholder = "Enter the imaginary part e.g. 0.6",
+        min_value = -1.0,
+        max_value = 1.0
+    )]
+    imaginary: f32,
+}
+
 #[async_trait]
 impl CommandHandler<CanisterRuntime> for Fractal {
     fn definition(&self) -> &BotCommandDefinition {
@@ -27,15 +45,14 @@ impl CommandHandler<CanisterRuntime> for Fractal {
         oc_client: Client<CanisterRuntime, BotCommandContext>,
     ) -> Result<SuccessResult, String> {
         let cxt = oc_client.context();
-        let r = cxt.command.arg("real");
-        let i = cxt.command.arg("imaginary");
+        let Args { real, imaginary } = cxt.command.parse_args().map_err(|e| e.to_string())?;
 
         let width = 400;
         let height = 400;
 
         let image_format = image::ImageFormat::Png;
 
-        let bytes = Fractal::generate(width, height, r, i)
+        let bytes = Fractal::generate(width, height, real, imaginary)
             .map_err(|error| format!("Failed to generate fractal: {error:?}"))?;
 
         let thumbnail_data = create_thumbnail(&bytes, image_format)
@@ -128,33 +145,11 @@ impl Fractal {
             name: "fractal".to_string(),
             description: Some("This will generate a Julia fractal based on the provided input values. Find some examples here: https://paulbourke.net/fractals/juliaset/".to_string()),
             placeholder: Some("Please wait".to_string()),
-            params: vec![
-                BotCommandParam {
-                    name: "real".to_string(),
-                    description: Some("The real part of the complex number input".to_string()),
-                    placeholder: Some("Enter the real part e.g. -0.4".to_string()), 
-                    required: true,
-                    param_type: BotCommandParamType::DecimalParam(DecimalParam {
-                        min_value: -1.0,
-                        max_value: 1.0,
-                        choices: vec![],
-                    }),
-                },
-                BotCommandParam {
-                    name: "imaginary".to_string(),
-                    description: Some("The imaginary part of the complex number input".to_string()),
-                    placeholder: Some("Enter the imaginary part e.g. 0.6".to_string()), 
-                    required: true,
-                    param_type: BotCommandParamType::DecimalParam(DecimalParam {
-                        min_value: -1.0,
-                        max_value: 1.0,
-                        choices: vec![],
-                    }),
-                },
-            ],
+            params: Args::params(),
             permissions: BotPermissions::from_message_permission(MessagePermission::Image),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
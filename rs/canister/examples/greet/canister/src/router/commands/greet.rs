@@ -51,6 +51,7 @@ impl Greet {
             permissions: BotPermissions::text_only(),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
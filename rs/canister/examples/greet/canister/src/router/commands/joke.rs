@@ -50,6 +50,7 @@ impl Joke {
             permissions: BotPermissions::text_only(),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
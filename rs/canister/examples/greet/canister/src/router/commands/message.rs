@@ -85,11 +85,13 @@ impl Message {
                     min_value: 1,
                     max_value: i32::MAX as i64,
                     choices: vec![],
+                    autocomplete: false,
                 }),
             }],
             permissions: BotPermissions::from_chat_permission(ChatPermission::ReadMessages),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
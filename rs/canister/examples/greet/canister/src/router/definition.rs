@@ -1,4 +1,4 @@
-use super::commands;
+use super::{commands, events};
 use oc_bots_sdk::api::definition::*;
 use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
 use std::collections::HashSet;
@@ -7,8 +7,9 @@ pub async fn get(_request: HttpRequest) -> HttpResponse {
     HttpResponse::json(
         200,
         &BotDefinition {
-            description: "This bot can greet you, tell jokes, and generate fractal images!"
-                .to_string(),
+            description:
+                "This bot can greet you and new members, tell jokes, and generate fractal images!"
+                    .to_string(),
             commands: commands::definitions(),
             autonomous_config: Some(AutonomousConfig {
                 permissions: BotPermissions::default()
@@ -18,6 +19,7 @@ pub async fn get(_request: HttpRequest) -> HttpResponse {
                     ]))
                     .with_message(&HashSet::from_iter(vec![MessagePermission::Text])),
                 sync_api_key: false,
+                subscriptions: events::subscriptions(),
             }),
         },
     )
@@ -0,0 +1,27 @@
+use crate::state;
+use oc_bots_sdk::api::event::BotEventHandlerRegistry;
+use oc_bots_sdk::types::ChatEventType;
+use oc_bots_sdk_canister::env::now;
+use oc_bots_sdk_canister::http_event_handler;
+use oc_bots_sdk_canister::CanisterRuntime;
+use oc_bots_sdk_canister::OPENCHAT_CLIENT_FACTORY;
+use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
+use std::sync::LazyLock;
+use welcome::Welcome;
+
+mod welcome;
+
+static EVENTS: LazyLock<BotEventHandlerRegistry<CanisterRuntime>> = LazyLock::new(|| {
+    BotEventHandlerRegistry::new(OPENCHAT_CLIENT_FACTORY.clone()).register(Welcome)
+});
+
+pub fn subscriptions() -> Vec<ChatEventType> {
+    EVENTS.subscriptions()
+}
+
+pub async fn notify(request: HttpRequest) -> HttpResponse {
+    let public_keys = state::read(|state| state.oc_public_keys().clone());
+    let now = now();
+
+    http_event_handler::notify(request, &EVENTS, &public_keys, now).await
+}
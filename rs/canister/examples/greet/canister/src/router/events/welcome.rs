@@ -0,0 +1,42 @@
+use crate::state;
+use async_trait::async_trait;
+use oc_bots_sdk::api::event::BotEventHandler;
+use oc_bots_sdk::oc_api::actions::send_message;
+use oc_bots_sdk::oc_api::actions::ActionArgsBuilder;
+use oc_bots_sdk::oc_api::client::Client;
+use oc_bots_sdk::types::{BotEventContext, ChatEvent, ChatEventType};
+use oc_bots_sdk_canister::CanisterRuntime;
+
+pub struct Welcome;
+
+#[async_trait]
+impl BotEventHandler<CanisterRuntime> for Welcome {
+    fn subscriptions(&self) -> &[ChatEventType] {
+        &[ChatEventType::MembershipUpdate]
+    }
+
+    async fn handle(
+        &self,
+        oc_client: Client<CanisterRuntime, BotEventContext>,
+    ) -> Result<(), String> {
+        let ChatEvent::ParticipantJoined(joined) = &oc_client.context().event.event else {
+            return Ok(());
+        };
+
+        let text = format!("Welcome @UserId({})!", joined.user_id);
+
+        // Send the message to OpenChat but don't wait for the response
+        oc_client
+            .send_text_message(text)
+            .execute(|args, response| match response {
+                Ok(send_message::Response::Success(_)) => {
+                    state::mutate(|state| state.increment_greets_sent());
+                }
+                error => {
+                    ic_cdk::println!("send_text_message: {args:?}, {error:?}");
+                }
+            });
+
+        Ok(())
+    }
+}
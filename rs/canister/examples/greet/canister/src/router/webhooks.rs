@@ -6,7 +6,7 @@ use oc_bots_sdk_canister::{env, HttpRequest, HttpResponse};
 
 pub async fn execute(request: HttpRequest) -> HttpResponse {
     let context =
-        match state::read(|state| request.extract_context(state.oc_public_key(), env::now())) {
+        match state::read(|state| request.extract_context(state.oc_public_keys(), env::now())) {
             Ok(cxt) => cxt,
             Err(response) => return response,
         };
@@ -1,4 +1,4 @@
-use oc_bots_sdk::oc_api::actions::{create_channel, ActionArgsBuilder};
+use oc_bots_sdk::oc_api::actions::{ActionArgsBuilder, ActionResponse};
 use oc_bots_sdk::types::BotApiKeyContext;
 use oc_bots_sdk_canister::{HttpRequest, HttpResponse, OPENCHAT_CLIENT_FACTORY};
 
@@ -18,13 +18,11 @@ pub async fn execute(request: HttpRequest, context: BotApiKeyContext) -> HttpRes
         .build(context)
         .create_channel(args.channel_name, args.is_public)
         .execute_async()
-        .await;
+        .await
+        .and_then(|response| response.into_result());
 
     match response {
-        Ok(create_channel::Response::Success(result)) => {
-            HttpResponse::text(200, result.channel_id.to_string())
-        }
-        Err((code, message)) => HttpResponse::text(500, format!("{}: {}", code, message)),
-        other => HttpResponse::text(500, format!("{:?}", other)),
+        Ok(result) => HttpResponse::text(200, result.channel_id.to_string()),
+        Err(error) => HttpResponse::text(500, error.to_string()),
     }
 }
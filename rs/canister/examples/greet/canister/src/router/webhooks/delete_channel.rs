@@ -1,4 +1,4 @@
-use oc_bots_sdk::oc_api::actions::{delete_channel, ActionArgsBuilder};
+use oc_bots_sdk::oc_api::actions::{ActionArgsBuilder, ActionResponse};
 use oc_bots_sdk::types::{BotApiKeyContext, ChannelId};
 use oc_bots_sdk_canister::{HttpRequest, HttpResponse, OPENCHAT_CLIENT_FACTORY};
 
@@ -17,11 +17,11 @@ pub async fn execute(request: HttpRequest, context: BotApiKeyContext) -> HttpRes
         .build(context)
         .delete_channel(args.channel_id)
         .execute_async()
-        .await;
+        .await
+        .and_then(|response| response.into_result());
 
     match response {
-        Ok(delete_channel::Response::Success) => HttpResponse::status(200),
-        Err((code, message)) => HttpResponse::text(500, format!("{}: {}", code, message)),
-        other => HttpResponse::text(500, format!("{:?}", other)),
+        Ok(()) => HttpResponse::status(200),
+        Err(error) => HttpResponse::text(500, error.to_string()),
     }
 }
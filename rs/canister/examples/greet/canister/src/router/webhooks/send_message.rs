@@ -1,4 +1,4 @@
-use oc_bots_sdk::oc_api::actions::{send_message, ActionArgsBuilder};
+use oc_bots_sdk::oc_api::actions::{ActionArgsBuilder, ActionResponse};
 use oc_bots_sdk::types::{BotApiKeyContext, MessageContentInitial, TextContent};
 use oc_bots_sdk_canister::{HttpRequest, HttpResponse, OPENCHAT_CLIENT_FACTORY};
 
@@ -17,11 +17,11 @@ pub async fn execute(request: HttpRequest, context: BotApiKeyContext) -> HttpRes
         .build(context)
         .send_message(MessageContentInitial::Text(TextContent { text: args.text }))
         .execute_async()
-        .await;
+        .await
+        .and_then(|response| response.into_result());
 
     match response {
-        Ok(send_message::Response::Success(result)) => HttpResponse::json(200, &result),
-        Err((code, message)) => HttpResponse::text(500, format!("{}: {}", code, message)),
-        other => HttpResponse::text(500, format!("{:?}", other)),
+        Ok(result) => HttpResponse::json(200, &result),
+        Err(error) => HttpResponse::text(500, error.to_string()),
     }
 }
@@ -1,6 +1,7 @@
 use crate::rng;
 use candid::Principal;
-use oc_bots_sdk_canister::env;
+use oc_bots_sdk::TrustedKeys;
+use oc_bots_sdk_canister::{env, trusted_keys_from_pem_or_trap};
 use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, collections::HashMap};
 
@@ -10,7 +11,8 @@ thread_local! {
 
 #[derive(Serialize, Deserialize)]
 pub struct State {
-    oc_public_key: String,
+    #[serde(alias = "oc_public_key")]
+    oc_public_keys: TrustedKeys,
     administrator: Principal,
     rng_seed: [u8; 32],
     jokes: HashMap<u32, String>,
@@ -46,7 +48,7 @@ pub fn take() -> State {
 impl State {
     pub fn new(oc_public_key: String, administrator: Principal) -> State {
         State {
-            oc_public_key,
+            oc_public_keys: trusted_keys_from_pem_or_trap(&oc_public_key),
             administrator,
             jokes: HashMap::new(),
             blobs: HashMap::new(),
@@ -70,8 +72,10 @@ impl State {
     }
 
     pub fn update(&mut self, oc_public_key: Option<String>, administrator: Option<Principal>) {
+        // Replaces all of the trusted keys. To rotate keys without downtime use the
+        // `add_oc_public_key` and `retire_oc_public_key` endpoints instead.
         if let Some(oc_public_key) = oc_public_key {
-            self.oc_public_key = oc_public_key;
+            self.oc_public_keys = trusted_keys_from_pem_or_trap(&oc_public_key);
         }
 
         if let Some(administrator) = administrator {
@@ -95,8 +99,12 @@ impl State {
         self.metrics.fractals_sent += 1;
     }
 
-    pub fn oc_public_key(&self) -> &str {
-        &self.oc_public_key
+    pub fn oc_public_keys(&self) -> &TrustedKeys {
+        &self.oc_public_keys
+    }
+
+    pub fn set_oc_public_keys(&mut self, oc_public_keys: TrustedKeys) {
+        self.oc_public_keys = oc_public_keys;
     }
 
     pub fn administrator(&self) -> &Principal {
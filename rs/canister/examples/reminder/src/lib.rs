@@ -1,4 +1,4 @@
-use crate::memory::get_upgrades_memory;
+use crate::memory::{get_outbox_memory, get_upgrades_memory};
 use candid::CandidType;
 use ic_cdk::{init, post_upgrade, pre_upgrade, query, update};
 use ic_http_certification::{HttpRequest, HttpResponse};
@@ -6,6 +6,7 @@ use ic_stable_structures::{
     reader::{BufferedReader, Reader},
     writer::{BufferedWriter, Writer},
 };
+use oc_bots_sdk_canister::outbox::{self, OutboxConfig};
 use serde::{Deserialize, Serialize};
 use state::State;
 
@@ -24,6 +25,8 @@ fn init(args: InitOrUpgradeArgs) {
 
     let state = State::new(args.oc_public_key);
     state::init(state);
+
+    init_outbox();
 }
 
 #[pre_upgrade]
@@ -52,6 +55,15 @@ fn post_upgrade(args: InitOrUpgradeArgs) {
     state.update(args.oc_public_key);
 
     state::init(state);
+
+    init_outbox();
+}
+
+fn init_outbox() {
+    outbox::init(
+        get_outbox_memory(),
+        OutboxConfig::default().with_on_response(model::reminders::on_reminder_sent),
+    );
 }
 
 #[query]
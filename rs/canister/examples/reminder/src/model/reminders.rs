@@ -4,11 +4,13 @@ use chrono::DateTime;
 use chrono_tz::Tz;
 use english_to_cron::str_cron_syntax;
 use ic_cdk_timers::TimerId;
-use oc_bots_sdk::oc_api::actions::{send_message, ActionArgsBuilder};
+use oc_bots_sdk::oc_api::actions::send_message::SendMessageAction;
+use oc_bots_sdk::oc_api::actions::ActionResponse;
 use oc_bots_sdk::types::{
-    ActionScope, BotApiKeyContext, BotPermissions, Chat, MessageContentInitial, TextContent,
+    ActionScope, BotPermissions, CallResult, Chat, MessageContentInitial, TextContent,
     TimestampMillis, UserId,
 };
+use oc_bots_sdk_canister::outbox::{self, PendingAction};
 use oc_bots_sdk_canister::{env, OPENCHAT_CLIENT_FACTORY};
 use serde::{Deserialize, Serialize};
 use std::cell::Cell;
@@ -58,12 +60,20 @@ fn run() {
                 &ActionScope::Chat(reminder.chat),
                 &BotPermissions::text_only(),
             ) {
-                ic_cdk::spawn(send_reminder(
-                    api_key.to_context(),
-                    reminder.message.clone(),
-                    reminder.chat,
-                    reminder.chat_reminder_id,
-                ));
+                let client = OPENCHAT_CLIENT_FACTORY.build(api_key.to_context());
+                let builder = client
+                    .send_message(MessageContentInitial::Text(TextContent {
+                        text: reminder.message.clone(),
+                    }))
+                    .with_channel_id(reminder.chat.channel_id())
+                    .with_block_level_markdown(true);
+
+                // The outbox persists the message and retries it until it is delivered
+                if let Err(error) =
+                    outbox::push_with_tag(builder, &(reminder.chat, reminder.chat_reminder_id))
+                {
+                    ic_cdk::println!("Failed to queue reminder: {}", error);
+                }
             } else {
                 continue;
             }
@@ -73,24 +83,24 @@ fn run() {
     });
 }
 
-async fn send_reminder(context: BotApiKeyContext, text: String, chat: Chat, chat_reminder_id: u8) {
-    match OPENCHAT_CLIENT_FACTORY
-        .build(context)
-        .send_message(MessageContentInitial::Text(TextContent { text }))
-        .with_channel_id(chat.channel_id())
-        .with_block_level_markdown(true)
-        .execute_async()
-        .await
+pub(crate) fn on_reminder_sent(action: PendingAction, result: CallResult<Vec<u8>>) {
+    let Some((chat, chat_reminder_id)) = action.tag::<(Chat, u8)>() else {
+        return;
+    };
+
+    match outbox::decode_response::<SendMessageAction>(result)
+        .and_then(|response| response.into_result())
     {
-        Ok(send_message::Response::Success(_)) => (),
-        Err((code, message)) => {
-            ic_cdk::println!("Failed to send reminder: {}: {}", code, message);
-        }
-        other => {
+        Ok(_) => (),
+        // Eg. the bot has been removed from the chat so the reminder can never be sent
+        Err(error) if error.is_permanent() => {
             mutate(|state| {
                 let _ = state.reminders.delete(&chat, chat_reminder_id);
             });
-            ic_cdk::println!("Failed to send reminder - DELETING: {:?}", other);
+            ic_cdk::println!("Failed to send reminder - DELETING: {}", error);
+        }
+        Err(error) => {
+            ic_cdk::println!("Failed to send reminder: {}", error);
         }
     }
 }
@@ -115,6 +125,10 @@ pub struct Reminder {
 }
 
 impl Reminder {
+    pub fn chat_reminder_id(&self) -> u8 {
+        self.chat_reminder_id
+    }
+
     pub fn to_text(&self) -> String {
         let mut message = self.message.trim().replace(['\n'], " ");
 
@@ -281,6 +295,33 @@ impl Reminders {
             .ok_or("Reminder not found".to_string())
     }
 
+    // Deletes the reminders in the given chat or, if the scope is a community, in all its channels
+    pub fn delete_all_within(&mut self, scope: &ActionScope) -> usize {
+        let chats: Vec<Chat> = self
+            .per_chat
+            .keys()
+            .filter(|chat| match (scope, chat) {
+                (ActionScope::Chat(c), _) => c == *chat,
+                (ActionScope::Community(community_id), Chat::Channel(id, _)) => id == community_id,
+                _ => false,
+            })
+            .copied()
+            .collect();
+
+        let mut deleted = 0;
+        for chat in chats {
+            if let Some(chat_reminders) = self.per_chat.remove(&chat) {
+                for global_id in chat_reminders.into_values() {
+                    // As with `delete`, the ordered set is cleaned up when each reminder falls due
+                    if self.reminders.remove(&global_id).is_some() {
+                        deleted += 1;
+                    }
+                }
+            }
+        }
+        deleted
+    }
+
     pub fn list(&self, chat: &Chat) -> Vec<Reminder> {
         self.per_chat
             .get(chat)
@@ -378,6 +419,7 @@ pub enum RemindWhen {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use candid::Principal;
 
     #[test]
     fn test_formatting() {
@@ -390,4 +432,45 @@ mod tests {
             "at 12:02 on Mon, 10 Mar 2025"
         );
     }
+
+    #[test]
+    fn delete_all_within_community_deletes_its_channels() {
+        let community = Principal::from_slice(&[1]);
+        let other_community = Principal::from_slice(&[2]);
+        let group = Principal::from_slice(&[3]);
+        let chats = [
+            Chat::Channel(community, 1),
+            Chat::Channel(community, 2),
+            Chat::Channel(other_community, 1),
+            Chat::Group(group),
+        ];
+
+        let mut reminders = Reminders::default();
+        for chat in chats {
+            reminders
+                .add(
+                    "Hello".to_string(),
+                    RemindWhen::Once(1_000),
+                    "Europe/London",
+                    Principal::anonymous().into(),
+                    chat,
+                    0,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(
+            reminders.delete_all_within(&ActionScope::Community(community)),
+            2
+        );
+        assert_eq!(reminders.count(), 2);
+        assert!(reminders.list(&Chat::Channel(community, 1)).is_empty());
+        assert_eq!(reminders.list(&Chat::Channel(other_community, 1)).len(), 1);
+
+        assert_eq!(
+            reminders.delete_all_within(&ActionScope::Chat(Chat::Group(group))),
+            1
+        );
+        assert_eq!(reminders.count(), 1);
+    }
 }
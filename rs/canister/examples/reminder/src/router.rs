@@ -4,6 +4,7 @@ use std::sync::LazyLock;
 
 mod commands;
 mod definition;
+mod events;
 mod metrics;
 
 static ROUTER: LazyLock<HttpRouter> = LazyLock::new(init_router);
@@ -11,6 +12,8 @@ static ROUTER: LazyLock<HttpRouter> = LazyLock::new(init_router);
 fn init_router() -> HttpRouter {
     HttpRouter::default()
         .route("/execute_command", POST, commands::execute)
+        .route("/autocomplete", POST, commands::autocomplete)
+        .route("/notify", POST, events::notify)
         .route("/metrics", GET, metrics::get)
         .fallback(definition::get)
 }
@@ -1,8 +1,9 @@
 use crate::state;
 use delete::Delete;
 use list::List;
-use oc_bots_sdk::api::command::CommandHandlerRegistry;
-use oc_bots_sdk::api::definition::BotCommandDefinition;
+use oc_bots_sdk::api::command::{CommandGroup, CommandHandlerRegistry};
+use oc_bots_sdk::api::definition::{BotCommandDefinition, BotPermissions};
+use oc_bots_sdk::types::ChatRole;
 use oc_bots_sdk_canister::env::now;
 use oc_bots_sdk_canister::http_command_handler;
 use oc_bots_sdk_canister::CanisterRuntime;
@@ -20,20 +21,43 @@ mod sync_api_key;
 
 static COMMANDS: LazyLock<CommandHandlerRegistry<CanisterRuntime>> = LazyLock::new(|| {
     CommandHandlerRegistry::new(OPENCHAT_CLIENT_FACTORY.clone())
-        .register(RemindRecurring)
-        .register(RemindAt)
-        .register(List)
-        .register(Delete)
+        .register_group(
+            CommandGroup::new(remind_definition())
+                .register(RemindAt)
+                .register(RemindRecurring)
+                .register(List)
+                .register(Delete),
+        )
         .on_sync_api_key(Box::new(sync_api_key::callback))
 });
 
+fn remind_definition() -> BotCommandDefinition {
+    BotCommandDefinition {
+        name: "remind".to_string(),
+        description: Some("Set, list and delete the reminders in this chat".to_string()),
+        placeholder: None,
+        params: vec![],
+        permissions: BotPermissions::default(),
+        default_role: Some(ChatRole::Admin),
+        direct_messages: None,
+        subcommands: vec![],
+    }
+}
+
 pub fn definitions() -> Vec<BotCommandDefinition> {
     COMMANDS.definitions()
 }
 
 pub async fn execute(request: HttpRequest) -> HttpResponse {
-    let public_key = state::read(|state| state.oc_public_key().to_string());
+    let public_keys = state::read(|state| state.oc_public_keys().clone());
+    let now = now();
+
+    http_command_handler::execute(request, &COMMANDS, &public_keys, now).await
+}
+
+pub async fn autocomplete(request: HttpRequest) -> HttpResponse {
+    let public_keys = state::read(|state| state.oc_public_keys().clone());
     let now = now();
 
-    http_command_handler::execute(request, &COMMANDS, &public_key, now).await
+    http_command_handler::autocomplete(request, &COMMANDS, &public_keys, now).await
 }
@@ -1,6 +1,9 @@
 use crate::state;
 use async_trait::async_trait;
-use oc_bots_sdk::api::command::{CommandHandler, EphemeralMessageBuilder, SuccessResult};
+use oc_bots_sdk::api::command::{
+    AutocompleteArgs, AutocompleteChoice, CommandArgValue, CommandHandler, EphemeralMessageBuilder,
+    SuccessResult,
+};
 use oc_bots_sdk::api::definition::{
     BotCommandDefinition, BotCommandParam, BotCommandParamType, IntegerParam,
 };
@@ -48,12 +51,45 @@ impl CommandHandler<CanisterRuntime> for Delete {
         .build()
         .into())
     }
+
+    async fn autocomplete(
+        &self,
+        oc_client: Client<CanisterRuntime, BotCommandContext>,
+        args: AutocompleteArgs,
+    ) -> Result<Vec<AutocompleteChoice>, String> {
+        let BotCommandScope::Chat(chat_scope) = &oc_client.context().scope else {
+            return Ok(Vec::new());
+        };
+
+        let input = args.input.trim().to_lowercase();
+
+        let mut matches: Vec<_> = state::read(|state| state.reminders.list(&chat_scope.chat))
+            .into_iter()
+            .filter_map(|reminder| {
+                let id_match = reminder.chat_reminder_id().to_string().starts_with(&input);
+
+                (id_match || reminder.to_text().to_lowercase().contains(&input))
+                    .then_some((id_match, reminder))
+            })
+            .collect();
+
+        // Rank reminders whose ID matches the input above those which only match on their text
+        matches.sort_by_key(|(id_match, reminder)| (!id_match, reminder.chat_reminder_id()));
+
+        Ok(matches
+            .into_iter()
+            .map(|(_, reminder)| AutocompleteChoice {
+                name: reminder.to_text(),
+                value: CommandArgValue::Integer(reminder.chat_reminder_id() as i64),
+            })
+            .collect())
+    }
 }
 
 impl Delete {
     fn definition() -> BotCommandDefinition {
         BotCommandDefinition {
-            name: "delete_reminder".to_string(),
+            name: "delete".to_string(),
             description: Some("Delete a reminder from this chat by ID".to_string()),
             placeholder: None,
             params: vec![BotCommandParam {
@@ -65,11 +101,13 @@ impl Delete {
                     choices: vec![],
                     min_value: 1,
                     max_value: 100,
+                    autocomplete: true,
                 }),
             }],
             permissions: BotPermissions::default(),
             default_role: Some(ChatRole::Admin),
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
@@ -55,13 +55,14 @@ impl CommandHandler<CanisterRuntime> for List {
 impl List {
     fn definition() -> BotCommandDefinition {
         BotCommandDefinition {
-            name: "list_reminders".to_string(),
+            name: "list".to_string(),
             description: Some("List the reminders set in this chat with their IDs".to_string()),
             placeholder: None,
             params: vec![],
             permissions: BotPermissions::default(),
             default_role: Some(ChatRole::Admin),
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
@@ -1,13 +1,14 @@
 use crate::model::reminders::{self, RemindWhen, Reminder};
 use crate::state;
 use async_trait::async_trait;
-use oc_bots_sdk::api::command::{CommandHandler, EphemeralMessageBuilder, SuccessResult};
-use oc_bots_sdk::api::definition::{
-    BotCommandDefinition, BotCommandParam, BotCommandParamType, DateTimeParam, StringParam,
+use oc_bots_sdk::api::command::{
+    CommandArgs, CommandHandler, EphemeralMessageBuilder, SuccessResult,
 };
+use oc_bots_sdk::api::definition::BotCommandDefinition;
 use oc_bots_sdk::oc_api::client::Client;
 use oc_bots_sdk::types::{
     BotCommandContext, BotCommandScope, BotPermissions, ChatRole, MessageContentInitial,
+    TimestampMillis,
 };
 use oc_bots_sdk_canister::{env, CanisterRuntime};
 use std::sync::LazyLock;
@@ -16,6 +17,24 @@ static DEFINITION: LazyLock<BotCommandDefinition> = LazyLock::new(RemindAt::defi
 
 pub struct RemindAt;
 
+#[derive(CommandArgs)]
+struct Args {
+    #[arg(
+        description = "The reminder message to be sent at the specified time. This supports `markdown` to style messages.",
+        placeholder = "Enter a reminder message...",
+        min_length = 1,
+        max_length = 5000,
+        multi_line
+    )]
+    what: String,
+    #[arg(
+        description = "The date and time to send the reminder",
+        placeholder = "Pick a date/time to send the reminder...",
+        future_only
+    )]
+    when: TimestampMillis,
+}
+
 #[async_trait]
 impl CommandHandler<CanisterRuntime> for RemindAt {
     fn definition(&self) -> &BotCommandDefinition {
@@ -27,8 +46,7 @@ impl CommandHandler<CanisterRuntime> for RemindAt {
         oc_client: Client<CanisterRuntime, BotCommandContext>,
     ) -> Result<SuccessResult, String> {
         let cxt = oc_client.context();
-        let what = cxt.command.arg("what");
-        let when = cxt.command.arg("when");
+        let Args { what, when } = cxt.command.parse_args().map_err(|e| e.to_string())?;
         let timezone = cxt.command.timezone();
 
         let text = state::mutate(|state| {
@@ -88,39 +106,16 @@ impl CommandHandler<CanisterRuntime> for RemindAt {
 impl RemindAt {
     fn definition() -> BotCommandDefinition {
         BotCommandDefinition {
-            name: "remind_at".to_string(),
-            description: Some("/remind_at \"Go to dentist appointment\" \"4pm tomorrow\"".to_string()),
+            name: "at".to_string(),
+            description: Some(
+                "/remind at \"Go to dentist appointment\" \"4pm tomorrow\"".to_string(),
+            ),
             placeholder: None,
-            params: vec![
-                BotCommandParam {
-                    name: "what".to_string(),
-                    description: Some(
-                        "The reminder message to be sent at the specified time. This supports `markdown` to style messages.".to_string(),
-                    ),
-                    placeholder: Some("Enter a reminder message...".to_string()),
-                    required: true,
-                    param_type: BotCommandParamType::StringParam(StringParam {
-                        choices: vec![],
-                        min_length: 1,
-                        max_length: 5000,
-                        multi_line: true,
-                    }),
-                },
-                BotCommandParam {
-                    name: "when".to_string(),
-                    description: Some(
-                        "The date and time to send the reminder".to_string(),
-                    ),
-                    placeholder: Some("Pick a date/time to send the reminder...".to_string()),
-                    required: true,
-                    param_type: BotCommandParamType::DateTimeParam(DateTimeParam {
-                        future_only: true,
-                    }),
-                },
-            ],
+            params: Args::params(),
             permissions: BotPermissions::default(),
             default_role: Some(ChatRole::Admin),
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
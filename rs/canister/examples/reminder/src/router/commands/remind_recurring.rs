@@ -89,8 +89,8 @@ impl CommandHandler<CanisterRuntime> for RemindRecurring {
 impl RemindRecurring {
     fn definition() -> BotCommandDefinition {
         BotCommandDefinition {
-            name: "remind_recurring".to_string(),
-            description: Some("/remind_recurring \"Daily stand-up starting now\" \"9am every weekday\"".to_string()),
+            name: "every".to_string(),
+            description: Some("/remind every \"Daily stand-up starting now\" \"9am every weekday\"".to_string()),
             placeholder: None,
             params: vec![
                 BotCommandParam {
@@ -105,6 +105,7 @@ impl RemindRecurring {
                         min_length: 1,
                         max_length: 5000,
                         multi_line: true,
+                        autocomplete: false,
                     }),
                 },
                 BotCommandParam {
@@ -119,12 +120,14 @@ impl RemindRecurring {
                         min_length: 1,
                         max_length: 200,
                         multi_line: false,
+                        autocomplete: false,
                     }),
                 },
             ],
             permissions: BotPermissions::default(),
             default_role: Some(ChatRole::Admin),
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
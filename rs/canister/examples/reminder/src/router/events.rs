@@ -0,0 +1,35 @@
+use crate::state;
+use oc_bots_sdk::api::event::BotEventHandlerRegistry;
+use oc_bots_sdk::types::{ActionScope, BotPermissions, ChatEventType};
+use oc_bots_sdk_canister::env::now;
+use oc_bots_sdk_canister::http_event_handler;
+use oc_bots_sdk_canister::CanisterRuntime;
+use oc_bots_sdk_canister::OPENCHAT_CLIENT_FACTORY;
+use oc_bots_sdk_canister::{HttpRequest, HttpResponse};
+use std::sync::LazyLock;
+
+static EVENTS: LazyLock<BotEventHandlerRegistry<CanisterRuntime>> = LazyLock::new(|| {
+    BotEventHandlerRegistry::new(OPENCHAT_CLIENT_FACTORY.clone())
+        .on_uninstalled(Box::new(on_uninstalled))
+});
+
+pub fn subscriptions() -> Vec<ChatEventType> {
+    EVENTS.subscriptions()
+}
+
+pub async fn notify(request: HttpRequest) -> HttpResponse {
+    let public_keys = state::read(|state| state.oc_public_keys().clone());
+    let now = now();
+
+    http_event_handler::notify(request, &EVENTS, &public_keys, now).await
+}
+
+// Stop sending reminders to a chat or community as soon as the bot is uninstalled from it
+fn on_uninstalled(scope: ActionScope, _permissions: BotPermissions) {
+    state::mutate(|state| {
+        state.api_key_registry.remove_all_within(&scope);
+        let deleted = state.reminders.delete_all_within(&scope);
+
+        ic_cdk::println!("Bot uninstalled from {scope:?}, deleted {deleted} reminders");
+    });
+}
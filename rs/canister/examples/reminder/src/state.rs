@@ -1,5 +1,6 @@
 use crate::model::reminders::{self, Reminders};
-use oc_bots_sdk::ApiKeyRegistry;
+use oc_bots_sdk::{ApiKeyRegistry, TrustedKeys};
+use oc_bots_sdk_canister::{outbox, trusted_keys_from_pem_or_trap};
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 
@@ -9,7 +10,8 @@ thread_local! {
 
 #[derive(Serialize, Deserialize)]
 pub struct State {
-    oc_public_key: String,
+    #[serde(alias = "oc_public_key")]
+    oc_public_keys: TrustedKeys,
     pub api_key_registry: ApiKeyRegistry,
     pub reminders: Reminders,
 }
@@ -42,7 +44,7 @@ pub fn take() -> State {
 impl State {
     pub fn new(oc_public_key: String) -> State {
         State {
-            oc_public_key,
+            oc_public_keys: trusted_keys_from_pem_or_trap(&oc_public_key),
             api_key_registry: ApiKeyRegistry::default(),
             reminders: Reminders::default(),
         }
@@ -50,14 +52,14 @@ impl State {
 
     pub fn update(&mut self, oc_public_key: Option<String>) {
         if let Some(oc_public_key) = oc_public_key {
-            self.oc_public_key = oc_public_key;
+            self.oc_public_keys = trusted_keys_from_pem_or_trap(&oc_public_key);
         }
 
         reminders::start_job_if_required(self);
     }
 
-    pub fn oc_public_key(&self) -> &str {
-        &self.oc_public_key
+    pub fn oc_public_keys(&self) -> &TrustedKeys {
+        &self.oc_public_keys
     }
 
     pub fn metrics(&self) -> Metrics {
@@ -65,6 +67,7 @@ impl State {
             api_keys: self.api_key_registry.count(),
             reminders: self.reminders.count(),
             chats_with_reminders: self.reminders.chats_count(),
+            pending_messages: outbox::len(),
         }
     }
 }
@@ -74,4 +77,5 @@ pub struct Metrics {
     pub api_keys: usize,
     pub reminders: usize,
     pub chats_with_reminders: usize,
+    pub pending_messages: u64,
 }
@@ -1,9 +1,13 @@
 use candid::utils::{ArgumentDecoder, ArgumentEncoder};
 use oc_bots_sdk::{
     oc_api::Runtime,
-    types::{CallResult, CanisterId, TimestampMillis},
+    types::{CallResult, CanisterId, OcApiError, TimestampMillis},
 };
 use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
 #[derive(Clone, Default)]
 pub struct CanisterRuntime;
@@ -17,7 +21,10 @@ impl Runtime for CanisterRuntime {
     ) -> CallResult<R> {
         match ic_cdk::api::call::call(canister_id, method_name, args).await {
             Ok(result) => Ok(result),
-            Err((code, msg)) => Err((code as i32, msg)),
+            Err((code, message)) => Err(OcApiError::Rejected {
+                code: code as i32,
+                message,
+            }),
         }
     }
 
@@ -25,6 +32,13 @@ impl Runtime for CanisterRuntime {
         ic_cdk::spawn(f)
     }
 
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        Sleep {
+            duration,
+            state: None,
+        }
+    }
+
     fn now(&self) -> TimestampMillis {
         crate::env::now()
     }
@@ -33,3 +47,44 @@ impl Runtime for CanisterRuntime {
         true
     }
 }
+
+// Resolves once a timer set for `duration` has fired. The timer is only set the first time the
+// future is polled.
+struct Sleep {
+    duration: Duration,
+    state: Option<Arc<Mutex<SleepState>>>,
+}
+
+#[derive(Default)]
+struct SleepState {
+    elapsed: bool,
+    waker: Option<Waker>,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let duration = self.duration;
+        let state = self.state.get_or_insert_with(|| {
+            let state = Arc::new(Mutex::new(SleepState::default()));
+            let state_clone = state.clone();
+            ic_cdk_timers::set_timer(duration, move || {
+                let mut state = state_clone.lock().unwrap();
+                state.elapsed = true;
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+            state
+        });
+
+        let mut state = state.lock().unwrap();
+        if state.elapsed {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
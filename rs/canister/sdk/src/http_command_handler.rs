@@ -1,12 +1,15 @@
 use crate::{CanisterRuntime, HttpRequest, HttpResponse};
-use oc_bots_sdk::api::command::{BadRequest, CommandHandlerRegistry, CommandResponse};
+use oc_bots_sdk::api::command::{
+    AutocompleteArgs, AutocompleteResponse, BadRequest, CommandHandlerRegistry, CommandResponse,
+};
+use oc_bots_sdk::jwt::JwtVerifier;
 use oc_bots_sdk::types::TimestampMillis;
 use std::str;
 
 pub async fn execute(
     request: HttpRequest,
     command_handlers: &CommandHandlerRegistry<CanisterRuntime>,
-    public_key: &str,
+    public_key: &(impl JwtVerifier + ?Sized),
     now: TimestampMillis,
 ) -> HttpResponse {
     let jwt = match request.get_header("x-oc-jwt") {
@@ -21,3 +24,29 @@ pub async fn execute(
         CommandResponse::InternalError(err) => HttpResponse::json(500, &err),
     }
 }
+
+pub async fn autocomplete(
+    request: HttpRequest,
+    command_handlers: &CommandHandlerRegistry<CanisterRuntime>,
+    public_key: &(impl JwtVerifier + ?Sized),
+    now: TimestampMillis,
+) -> HttpResponse {
+    let jwt = match request.get_header("x-oc-jwt") {
+        Some(jwt) => jwt,
+        None => return HttpResponse::json(400, &BadRequest::AccessTokenNotFound),
+    };
+
+    let args: AutocompleteArgs = match request.extract_args() {
+        Ok(args) => args,
+        Err(response) => return response,
+    };
+
+    match command_handlers
+        .autocomplete(jwt, public_key, now, args)
+        .await
+    {
+        AutocompleteResponse::Success(result) => HttpResponse::json(200, &result),
+        AutocompleteResponse::BadRequest(err) => HttpResponse::json(400, &err),
+        AutocompleteResponse::InternalError(err) => HttpResponse::json(500, &err),
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{CanisterRuntime, HttpRequest, HttpResponse};
+use oc_bots_sdk::api::command::BadRequest;
+use oc_bots_sdk::api::event::{BotEventHandlerRegistry, NotifyResponse};
+use oc_bots_sdk::jwt::JwtVerifier;
+use oc_bots_sdk::types::TimestampMillis;
+
+pub async fn notify(
+    request: HttpRequest,
+    event_handlers: &BotEventHandlerRegistry<CanisterRuntime>,
+    public_key: &(impl JwtVerifier + ?Sized),
+    now: TimestampMillis,
+) -> HttpResponse {
+    let jwt = match request.get_header("x-oc-jwt") {
+        Some(jwt) => jwt,
+        None => return HttpResponse::json(400, &BadRequest::AccessTokenNotFound),
+    };
+
+    match event_handlers.notify(jwt, public_key, now).await {
+        NotifyResponse::Success => HttpResponse::status(200),
+        NotifyResponse::BadRequest(err) => HttpResponse::json(400, &err),
+        NotifyResponse::InternalError(err) => HttpResponse::json(500, &err),
+    }
+}
@@ -1,9 +1,9 @@
 use crate::async_handler::{AsyncHandler, BoxedHandler};
 use ic_http_certification::HttpRequest as CanisterHttpRequest;
 use ic_http_certification::HttpResponse as CanisterHttpResponse;
+use oc_bots_sdk::jwt::JwtVerifier;
 use oc_bots_sdk::types::BotApiKeyContext;
 use oc_bots_sdk::types::TimestampMillis;
-use oc_bots_sdk::types::TokenError;
 use serde::Deserialize;
 use serde::Serialize;
 use std::str::FromStr;
@@ -117,7 +117,7 @@ impl HttpRequest {
 
     pub fn extract_context(
         &self,
-        public_key: &str,
+        public_key: &(impl JwtVerifier + ?Sized),
         now: TimestampMillis,
     ) -> Result<BotApiKeyContext, HttpResponse> {
         if let Some(jwt) = self.get_header("x-oc-jwt") {
@@ -125,7 +125,7 @@ impl HttpRequest {
         } else if let Some(api_key) = self.get_header("x-oc-api-key") {
             BotApiKeyContext::parse_api_key(api_key.to_string())
         } else {
-            Err(TokenError::Invalid("No auth token found".to_string()))
+            return Err(HttpResponse::text(400, "No auth token found".to_string()));
         }
         .map_err(|err| HttpResponse::text(400, format!("{err:?}")))
     }
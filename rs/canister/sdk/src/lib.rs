@@ -2,13 +2,22 @@ mod async_handler;
 mod canister_runtime;
 pub mod env;
 pub mod http_command_handler;
+pub mod http_event_handler;
 mod http_router;
+pub mod outbox;
 
 pub use http_router::*;
 
 pub use canister_runtime::CanisterRuntime;
 use oc_bots_sdk::oc_api::client::ClientFactory;
+use oc_bots_sdk::TrustedKeys;
 use std::sync::{Arc, LazyLock};
 
 pub static OPENCHAT_CLIENT_FACTORY: LazyLock<Arc<ClientFactory<CanisterRuntime>>> =
     LazyLock::new(|| Arc::new(ClientFactory::new(CanisterRuntime)));
+
+// Traps if the key is invalid, so when called from `init` or `post_upgrade` the install or upgrade
+// is rejected rather than leaving the bot unable to verify any tokens
+pub fn trusted_keys_from_pem_or_trap(oc_public_key: &str) -> TrustedKeys {
+    TrustedKeys::from_pem(oc_public_key).expect("Invalid OpenChat public key")
+}
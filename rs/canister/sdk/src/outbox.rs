@@ -0,0 +1,520 @@
+//! A persistent queue of actions to be sent to OpenChat.
+//!
+//! Actions pushed to the outbox are stored in stable memory before any call is made, so they
+//! survive upgrades and failed calls. They are then sent in the background, with at most
+//! `max_concurrency` calls in flight at once, and retried according to the outbox's
+//! `RetryPolicy` if either the call fails or OpenChat returns a transient error. Delivery is
+//! at-least-once.
+//!
+//! ```ignore
+//! #[init]
+//! fn init() {
+//!     outbox::init(get_outbox_memory(), OutboxConfig::default());
+//! }
+//!
+//! #[post_upgrade]
+//! fn post_upgrade() {
+//!     outbox::init(get_outbox_memory(), OutboxConfig::default());
+//! }
+//!
+//! let client = OPENCHAT_CLIENT_FACTORY.build(context);
+//! outbox::push(client.send_text_message(text))?;
+//! ```
+use crate::{env, CanisterRuntime};
+use candid::CandidType;
+use ic_cdk_timers::TimerId;
+use ic_stable_structures::memory_manager::VirtualMemory;
+use ic_stable_structures::storable::Bound;
+use ic_stable_structures::{DefaultMemoryImpl, StableBTreeMap, Storable};
+use oc_bots_sdk::oc_api::actions::{self, ActionArgsBuilder, ActionDef, ActionResponse};
+use oc_bots_sdk::oc_api::RetryPolicy;
+use oc_bots_sdk::types::{CallResult, CanisterId, OcApiError, TimestampMillis};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+pub type Memory = VirtualMemory<DefaultMemoryImpl>;
+pub type OnResponse = fn(PendingAction, CallResult<Vec<u8>>);
+
+const DEFAULT_MAX_CONCURRENCY: usize = 10;
+const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+const DEFAULT_MAX_BACKOFF: u64 = 5 * 60 * 1000; // 5 minutes
+const OUTBOX_ALREADY_INITIALIZED: &str = "The outbox has already been initialized";
+const OUTBOX_NOT_INITIALIZED: &str = "The outbox has not been initialized";
+
+thread_local! {
+    static OUTBOX: RefCell<Option<Outbox>> = RefCell::default();
+}
+
+type ResultDecoder = fn(&[u8]) -> CallResult<()>;
+
+// Decodes the response to each action which can be pushed, keyed by method name, so that
+// failures reported by OpenChat are retried as well as failures to make the call
+static RESULT_DECODERS: LazyLock<HashMap<&'static str, ResultDecoder>> = LazyLock::new(|| {
+    HashMap::from([
+        result_decoder::<actions::add_members::AddMembersAction>(),
+        result_decoder::<actions::add_reaction::AddReactionAction>(),
+        result_decoder::<actions::change_role::ChangeRoleAction>(),
+        result_decoder::<actions::create_channel::CreateChannelAction>(),
+        result_decoder::<actions::delete_channel::DeleteChannelAction>(),
+        result_decoder::<actions::delete_messages::DeleteMessagesAction>(),
+        result_decoder::<actions::edit_message::EditMessageAction>(),
+        result_decoder::<actions::invite_users::InviteUsersAction>(),
+        result_decoder::<actions::pin_message::PinMessageAction>(),
+        result_decoder::<actions::remove_reaction::RemoveReactionAction>(),
+        result_decoder::<actions::remove_user::RemoveUserAction>(),
+        result_decoder::<actions::send_message::SendMessageAction>(),
+        result_decoder::<actions::unpin_message::UnpinMessageAction>(),
+        result_decoder::<actions::update_chat::UpdateChatAction>(),
+    ])
+});
+
+pub struct OutboxConfig {
+    max_concurrency: usize,
+    retry_policy: RetryPolicy,
+    on_response: Option<OnResponse>,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        OutboxConfig {
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            retry_policy: RetryPolicy::new(DEFAULT_MAX_ATTEMPTS)
+                .with_max_backoff(DEFAULT_MAX_BACKOFF),
+            on_response: None,
+        }
+    }
+}
+
+impl OutboxConfig {
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    // Called once for each action when it leaves the outbox, either with the raw response from
+    // OpenChat (see `decode_response`) or with the error from the final attempt
+    pub fn with_on_response(mut self, on_response: OnResponse) -> Self {
+        self.on_response = Some(on_response);
+        self
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct PendingAction {
+    id: u64,
+    api_gateway: CanisterId,
+    method_name: String,
+    args: Vec<u8>,
+    tag: Option<Vec<u8>>,
+    attempts: u32,
+    next_attempt_at: TimestampMillis,
+}
+
+impl PendingAction {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn method_name(&self) -> &str {
+        &self.method_name
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    // The tag passed to `push_with_tag`, if any
+    pub fn tag<T: CandidType + DeserializeOwned>(&self) -> Option<T> {
+        self.tag
+            .as_ref()
+            .and_then(|bytes| candid::decode_one(bytes).ok())
+    }
+}
+
+impl Storable for PendingAction {
+    fn to_bytes(&self) -> Cow<[u8]> {
+        Cow::Owned(candid::encode_one(self).unwrap())
+    }
+
+    fn from_bytes(bytes: Cow<[u8]>) -> Self {
+        candid::decode_one(&bytes).unwrap()
+    }
+
+    const BOUND: Bound = Bound::Unbounded;
+}
+
+struct Outbox {
+    pending: StableBTreeMap<u64, PendingAction, Memory>,
+    queue: Queue,
+    next_id: u64,
+    config: OutboxConfig,
+    timer: Option<(TimerId, TimestampMillis)>,
+}
+
+// Tracks when each pending action is next due so that finding the due actions, or the time at
+// which the next one falls due, doesn't require a scan of stable memory. This is held in the heap
+// and rebuilt from the pending actions by `init`.
+#[derive(Default)]
+struct Queue {
+    waiting: BTreeSet<(TimestampMillis, u64)>,
+    in_flight: BTreeSet<u64>,
+}
+
+// Must be called in both `init` and `post_upgrade`. Any actions which were pending before an
+// upgrade are picked up again once the upgrade has completed.
+pub fn init(memory: Memory, config: OutboxConfig) {
+    OUTBOX.with_borrow_mut(|outbox| {
+        if outbox.is_some() {
+            panic!("{}", OUTBOX_ALREADY_INITIALIZED);
+        }
+
+        let pending: StableBTreeMap<u64, PendingAction, Memory> = StableBTreeMap::init(memory);
+        let next_id = pending.last_key_value().map_or(0, |(id, _)| id + 1);
+
+        // Calls in flight during an upgrade are abandoned, so every pending action is queued
+        let mut queue = Queue::default();
+        for (id, action) in pending.iter() {
+            queue.push(id, action.next_attempt_at);
+        }
+
+        *outbox = Some(Outbox {
+            pending,
+            queue,
+            next_id,
+            config,
+            timer: None,
+        });
+    });
+
+    // Calls can't be made during `init` or `post_upgrade` so start sending from a timer
+    mutate(|outbox| outbox.schedule(env::now()));
+}
+
+pub fn push<B: ActionArgsBuilder<CanisterRuntime>>(builder: B) -> CallResult<u64> {
+    push_internal(builder, None)
+}
+
+// The tag is stored alongside the action and is available to the `on_response` callback via
+// `PendingAction::tag`, eg. to identify which entity the action relates to
+pub fn push_with_tag<B: ActionArgsBuilder<CanisterRuntime>, T: CandidType>(
+    builder: B,
+    tag: &T,
+) -> CallResult<u64> {
+    let tag =
+        candid::encode_one(tag).map_err(|error| OcApiError::InvalidRequest(error.to_string()))?;
+
+    push_internal(builder, Some(tag))
+}
+
+pub fn len() -> u64 {
+    read(|outbox| outbox.pending.len())
+}
+
+pub fn decode_response<A: ActionDef>(result: CallResult<Vec<u8>>) -> CallResult<A::Response> {
+    result.and_then(|bytes| {
+        candid::decode_one(&bytes).map_err(|error| OcApiError::Decode(error.to_string()))
+    })
+}
+
+fn push_internal<B: ActionArgsBuilder<CanisterRuntime>>(
+    builder: B,
+    tag: Option<Vec<u8>>,
+) -> CallResult<u64> {
+    builder.check_permissions()?;
+
+    let api_gateway = builder.api_gateway();
+    let method_name = B::Action::method_name(true).to_string();
+    let args = candid::encode_one(builder.into_args())
+        .map_err(|error| OcApiError::InvalidRequest(error.to_string()))?;
+    let now = env::now();
+
+    Ok(mutate(|outbox| {
+        let id = outbox.next_id;
+        outbox.next_id += 1;
+        outbox.pending.insert(
+            id,
+            PendingAction {
+                id,
+                api_gateway,
+                method_name,
+                args,
+                tag,
+                attempts: 0,
+                next_attempt_at: now,
+            },
+        );
+        outbox.queue.push(id, now);
+        outbox.schedule(now);
+        id
+    }))
+}
+
+fn run() {
+    let now = env::now();
+
+    let due = mutate(|outbox| {
+        outbox.timer = None;
+
+        let due: Vec<_> = outbox
+            .queue
+            .take_due(now, outbox.config.max_concurrency)
+            .into_iter()
+            .filter_map(|id| outbox.pending.get(&id))
+            .collect();
+
+        // If there is no spare capacity the next batch is picked up as in-flight calls complete
+        if outbox.queue.has_capacity(outbox.config.max_concurrency) {
+            if let Some(next_attempt_at) = outbox.queue.next_attempt_at() {
+                outbox.schedule(next_attempt_at);
+            }
+        }
+
+        due
+    });
+
+    for action in due {
+        ic_cdk::spawn(send(action));
+    }
+}
+
+async fn send(action: PendingAction) {
+    let result =
+        ic_cdk::api::call::call_raw(action.api_gateway, &action.method_name, &action.args, 0)
+            .await
+            .map_err(|(code, message)| OcApiError::Rejected {
+                code: code as i32,
+                message,
+            });
+
+    let completed = mutate(|outbox| {
+        let completed = outbox.complete(action, &result, env::now());
+
+        if let Some(next_attempt_at) = outbox.queue.next_attempt_at() {
+            outbox.schedule(next_attempt_at);
+        }
+
+        completed
+    });
+
+    if let Some((action, on_response)) = completed {
+        on_response(action, result);
+    }
+}
+
+fn result_decoder<A: ActionDef>() -> (&'static str, ResultDecoder) {
+    let decode: ResultDecoder = |bytes| {
+        candid::decode_one::<A::Response>(bytes)
+            .map_err(|error| OcApiError::Decode(error.to_string()))?
+            .into_result()
+            .map(|_| ())
+    };
+
+    (A::method_name(true), decode)
+}
+
+// The outcome of the action, including any failure reported by OpenChat in the response
+fn action_result(method_name: &str, result: &CallResult<Vec<u8>>) -> CallResult<()> {
+    let bytes = result.as_ref().map_err(|error| error.clone())?;
+
+    RESULT_DECODERS
+        .get(method_name)
+        .map_or(Ok(()), |decode| decode(bytes))
+}
+
+// The time at which to make the next attempt, or `None` if the action has succeeded, has failed
+// permanently or has used up all of its attempts
+fn retry_at(
+    result: &CallResult<()>,
+    attempts: u32,
+    retry_policy: &RetryPolicy,
+    now: TimestampMillis,
+) -> Option<TimestampMillis> {
+    let retry = matches!(result, Err(error) if error.is_retryable())
+        && attempts < retry_policy.max_attempts();
+
+    retry.then(|| now + retry_policy.backoff(attempts).as_millis() as TimestampMillis)
+}
+
+impl Queue {
+    fn push(&mut self, id: u64, at: TimestampMillis) {
+        self.waiting.insert((at, id));
+    }
+
+    // Marks the actions which are due as in flight, taking no more than there is capacity for
+    fn take_due(&mut self, now: TimestampMillis, max_concurrency: usize) -> Vec<u64> {
+        let mut due = Vec::new();
+
+        while self.has_capacity(max_concurrency) {
+            let Some(&(at, id)) = self.waiting.first() else {
+                break;
+            };
+            if at > now {
+                break;
+            }
+
+            self.waiting.pop_first();
+            self.in_flight.insert(id);
+            due.push(id);
+        }
+
+        due
+    }
+
+    // Queues the action again if it is to be retried
+    fn complete(&mut self, id: u64, retry_at: Option<TimestampMillis>) {
+        self.in_flight.remove(&id);
+
+        if let Some(at) = retry_at {
+            self.push(id, at);
+        }
+    }
+
+    fn has_capacity(&self, max_concurrency: usize) -> bool {
+        self.in_flight.len() < max_concurrency
+    }
+
+    fn next_attempt_at(&self) -> Option<TimestampMillis> {
+        self.waiting.first().map(|(at, _)| *at)
+    }
+}
+
+impl Outbox {
+    // Records the result of an attempt, either queueing the action to be retried or removing it.
+    // Returns the action along with the `on_response` callback if it has left the outbox.
+    fn complete(
+        &mut self,
+        mut action: PendingAction,
+        result: &CallResult<Vec<u8>>,
+        now: TimestampMillis,
+    ) -> Option<(PendingAction, OnResponse)> {
+        action.attempts += 1;
+
+        let retry_at = retry_at(
+            &action_result(&action.method_name, result),
+            action.attempts,
+            &self.config.retry_policy,
+            now,
+        );
+        self.queue.complete(action.id, retry_at);
+
+        if let Some(retry_at) = retry_at {
+            action.next_attempt_at = retry_at;
+            self.pending.insert(action.id, action);
+            None
+        } else {
+            self.pending.remove(&action.id);
+            self.config
+                .on_response
+                .map(|on_response| (action, on_response))
+        }
+    }
+
+    // Ensures the job runs no later than `at`
+    fn schedule(&mut self, at: TimestampMillis) {
+        if let Some((timer_id, scheduled_at)) = self.timer {
+            if scheduled_at <= at {
+                return;
+            }
+            ic_cdk_timers::clear_timer(timer_id);
+        }
+
+        let delay = Duration::from_millis(at.saturating_sub(env::now()));
+        let timer_id = ic_cdk_timers::set_timer(delay, run);
+        self.timer = Some((timer_id, at));
+    }
+}
+
+fn read<F: FnOnce(&Outbox) -> R, R>(f: F) -> R {
+    OUTBOX.with_borrow(|outbox| f(outbox.as_ref().expect(OUTBOX_NOT_INITIALIZED)))
+}
+
+fn mutate<F: FnOnce(&mut Outbox) -> R, R>(f: F) -> R {
+    OUTBOX.with_borrow_mut(|outbox| f(outbox.as_mut().expect(OUTBOX_NOT_INITIALIZED)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_stable_structures::memory_manager::{MemoryId, MemoryManager};
+    use oc_bots_sdk::oc_api::actions::send_message;
+    use oc_bots_sdk::types::{OCError, OCErrorCode};
+
+    #[test]
+    fn due_actions_are_taken_in_order_up_to_capacity() {
+        let mut queue = Queue::default();
+        queue.push(1, 300);
+        queue.push(2, 100);
+        queue.push(3, 200);
+        queue.push(4, 1_000);
+
+        assert_eq!(queue.take_due(500, 2), [2, 3]);
+        assert!(!queue.has_capacity(2));
+        assert!(queue.take_due(500, 2).is_empty());
+        assert_eq!(queue.next_attempt_at(), Some(300));
+
+        queue.complete(2, None);
+        queue.complete(3, Some(400));
+        assert_eq!(queue.take_due(500, 2), [1, 3]);
+        assert_eq!(queue.next_attempt_at(), Some(1_000));
+    }
+
+    #[test]
+    fn only_retryable_failures_are_retried() {
+        let retry_policy = RetryPolicy::new(3).with_initial_backoff(1_000);
+        let timeout = Err(OcApiError::Timeout);
+
+        assert_eq!(retry_at(&timeout, 1, &retry_policy, 0), Some(1_000));
+        assert_eq!(retry_at(&timeout, 2, &retry_policy, 0), Some(2_000));
+        assert_eq!(retry_at(&timeout, 3, &retry_policy, 0), None);
+        assert_eq!(retry_at(&Ok(()), 1, &retry_policy, 0), None);
+        assert_eq!(
+            retry_at(&Err(OcApiError::NotAuthorized), 1, &retry_policy, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn transient_errors_from_openchat_are_retried() {
+        let memory = MemoryManager::init(DefaultMemoryImpl::default()).get(MemoryId::new(0));
+        let mut outbox = Outbox {
+            pending: StableBTreeMap::init(memory),
+            queue: Queue::default(),
+            next_id: 1,
+            config: OutboxConfig::default()
+                .with_retry_policy(RetryPolicy::new(3).with_initial_backoff(1_000)),
+            timer: None,
+        };
+        let action = PendingAction {
+            id: 0,
+            api_gateway: CanisterId::anonymous(),
+            method_name: "bot_send_message".to_string(),
+            args: Vec::new(),
+            tag: None,
+            attempts: 0,
+            next_attempt_at: 0,
+        };
+        outbox.pending.insert(0, action.clone());
+        outbox.queue.push(0, 0);
+        assert_eq!(outbox.queue.take_due(0, 1), [0]);
+
+        let response =
+            send_message::Response::Error(OCError::new(OCErrorCode::InternalError, None));
+        let result = Ok(candid::encode_one(response).unwrap());
+        assert!(outbox.complete(action, &result, 500).is_none());
+
+        let pending = outbox.pending.get(&0).unwrap();
+        assert_eq!(pending.attempts, 1);
+        assert_eq!(pending.next_attempt_at, 1_500);
+        assert_eq!(outbox.queue.next_attempt_at(), Some(1_500));
+    }
+}
@@ -0,0 +1,457 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::{
+    Data, DeriveInput, Expr, Field, Fields, GenericArgument, Ident, LitStr, PathArguments, Type,
+};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "CommandArgs cannot be derived for generic types",
+        ));
+    }
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "CommandArgs can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "CommandArgs can only be derived for structs with named fields",
+        ));
+    };
+
+    let args = fields
+        .named
+        .iter()
+        .map(Arg::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let params = args.iter().map(Arg::param);
+    let extractors = args.iter().map(Arg::extractor);
+    let field_idents = args.iter().map(|arg| &arg.ident);
+
+    Ok(quote! {
+        impl ::oc_bots_sdk::api::command::CommandArgs for #ident {
+            fn params() -> ::std::vec::Vec<::oc_bots_sdk::api::definition::BotCommandParam> {
+                ::std::vec![#(#params),*]
+            }
+        }
+
+        impl ::std::convert::TryFrom<&::oc_bots_sdk::api::command::Command> for #ident {
+            type Error = ::oc_bots_sdk::api::command::ArgError;
+
+            fn try_from(
+                command: &::oc_bots_sdk::api::command::Command,
+            ) -> ::std::result::Result<Self, Self::Error> {
+                #(#extractors)*
+
+                ::std::result::Result::Ok(#ident { #(#field_idents),* })
+            }
+        }
+    })
+}
+
+struct Arg {
+    ident: Ident,
+    name: String,
+    ty: Type,
+    required: bool,
+    kind: ArgKind,
+    attrs: ArgAttrs,
+}
+
+enum ArgKind {
+    String,
+    Integer { min: TokenStream, max: TokenStream },
+    Decimal { min: TokenStream, max: TokenStream },
+    Boolean,
+    User,
+    DateTime,
+}
+
+#[derive(Default)]
+struct ArgAttrs {
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+    placeholder: Option<LitStr>,
+    min_length: Option<Expr>,
+    max_length: Option<Expr>,
+    min_value: Option<Expr>,
+    max_value: Option<Expr>,
+    multi_line: bool,
+    autocomplete: bool,
+    datetime: bool,
+    future_only: bool,
+    choices: Vec<(LitStr, Expr)>,
+}
+
+impl Arg {
+    fn parse(field: &Field) -> syn::Result<Arg> {
+        let ident = field.ident.clone().expect("named field");
+        let attrs = ArgAttrs::parse(field)?;
+
+        let (ty, required) = match option_inner_type(&field.ty) {
+            Some(inner) => (inner.clone(), false),
+            None => (field.ty.clone(), true),
+        };
+
+        let kind = ArgKind::infer(&ty, &attrs)?;
+        kind.validate(&attrs, field)?;
+
+        let name = attrs
+            .name
+            .as_ref()
+            .map(|name| name.value())
+            .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+
+        if name.is_empty() {
+            return Err(syn::Error::new_spanned(field, "arg name cannot be empty"));
+        }
+
+        Ok(Arg {
+            ident,
+            name,
+            ty,
+            required,
+            kind,
+            attrs,
+        })
+    }
+
+    fn param(&self) -> TokenStream {
+        let name = &self.name;
+        let description = optional_string(self.attrs.description.as_ref());
+        let placeholder = optional_string(self.attrs.placeholder.as_ref());
+        let required = self.required;
+        let param_type = self.param_type();
+
+        quote! {
+            ::oc_bots_sdk::api::definition::BotCommandParam {
+                name: #name.to_string(),
+                description: #description,
+                placeholder: #placeholder,
+                required: #required,
+                param_type: #param_type,
+            }
+        }
+    }
+
+    fn param_type(&self) -> TokenStream {
+        let attrs = &self.attrs;
+
+        match &self.kind {
+            ArgKind::String => {
+                let min_length = attrs
+                    .min_length
+                    .as_ref()
+                    .map_or_else(|| quote!(0), |expr| quote!(#expr));
+                let max_length = attrs
+                    .max_length
+                    .as_ref()
+                    .map_or_else(|| quote!(u16::MAX), |expr| quote!(#expr));
+                let multi_line = attrs.multi_line;
+                let autocomplete = attrs.autocomplete;
+                let choices = attrs.choices.iter().map(|(name, value)| {
+                    choice(name, quote!(::std::string::ToString::to_string(&#value)))
+                });
+
+                quote! {
+                    ::oc_bots_sdk::api::definition::BotCommandParamType::StringParam(
+                        ::oc_bots_sdk::api::definition::StringParam {
+                            min_length: #min_length,
+                            max_length: #max_length,
+                            choices: ::std::vec![#(#choices),*],
+                            multi_line: #multi_line,
+                            autocomplete: #autocomplete,
+                        }
+                    )
+                }
+            }
+            ArgKind::Integer { min, max } => {
+                let min_value = attrs
+                    .min_value
+                    .as_ref()
+                    .map_or_else(|| min.clone(), |expr| quote!(#expr));
+                let max_value = attrs
+                    .max_value
+                    .as_ref()
+                    .map_or_else(|| max.clone(), |expr| quote!(#expr));
+                let choices = attrs
+                    .choices
+                    .iter()
+                    .map(|(name, value)| choice(name, quote!(#value)));
+                let autocomplete = attrs.autocomplete;
+
+                quote! {
+                    ::oc_bots_sdk::api::definition::BotCommandParamType::IntegerParam(
+                        ::oc_bots_sdk::api::definition::IntegerParam {
+                            min_value: #min_value,
+                            max_value: #max_value,
+                            choices: ::std::vec![#(#choices),*],
+                            autocomplete: #autocomplete,
+                        }
+                    )
+                }
+            }
+            ArgKind::Decimal { min, max } => {
+                let min_value = attrs
+                    .min_value
+                    .as_ref()
+                    .map_or_else(|| min.clone(), |expr| quote!(#expr));
+                let max_value = attrs
+                    .max_value
+                    .as_ref()
+                    .map_or_else(|| max.clone(), |expr| quote!(#expr));
+                let choices = attrs
+                    .choices
+                    .iter()
+                    .map(|(name, value)| choice(name, quote!(#value)));
+
+                quote! {
+                    ::oc_bots_sdk::api::definition::BotCommandParamType::DecimalParam(
+                        ::oc_bots_sdk::api::definition::DecimalParam {
+                            min_value: #min_value,
+                            max_value: #max_value,
+                            choices: ::std::vec![#(#choices),*],
+                        }
+                    )
+                }
+            }
+            ArgKind::Boolean => {
+                quote!(::oc_bots_sdk::api::definition::BotCommandParamType::BooleanParam)
+            }
+            ArgKind::User => {
+                quote!(::oc_bots_sdk::api::definition::BotCommandParamType::UserParam)
+            }
+            ArgKind::DateTime => {
+                let future_only = attrs.future_only;
+
+                quote! {
+                    ::oc_bots_sdk::api::definition::BotCommandParamType::DateTimeParam(
+                        ::oc_bots_sdk::api::definition::DateTimeParam {
+                            future_only: #future_only,
+                        }
+                    )
+                }
+            }
+        }
+    }
+
+    fn extractor(&self) -> TokenStream {
+        let ident = &self.ident;
+        let name = &self.name;
+        let ty = &self.ty;
+
+        if self.required {
+            quote! {
+                let #ident = command.try_arg::<#ty>(#name)?;
+            }
+        } else {
+            quote! {
+                let #ident = command.try_maybe_arg::<#ty>(#name)?;
+            }
+        }
+    }
+}
+
+impl ArgKind {
+    fn infer(ty: &Type, attrs: &ArgAttrs) -> syn::Result<ArgKind> {
+        let Some(type_name) = type_name(ty) else {
+            return Err(syn::Error::new_spanned(ty, "unsupported arg type"));
+        };
+
+        if attrs.datetime || attrs.future_only {
+            return if type_name == "u64" || type_name == "TimestampMillis" {
+                Ok(ArgKind::DateTime)
+            } else {
+                Err(syn::Error::new_spanned(
+                    ty,
+                    "datetime args must be of type `TimestampMillis` or `u64`",
+                ))
+            };
+        }
+
+        let kind = match type_name.as_str() {
+            "String" => ArgKind::String,
+            "i8" | "i16" | "i32" => ArgKind::Integer {
+                min: quote!(#ty::MIN as i64),
+                max: quote!(#ty::MAX as i64),
+            },
+            "i64" => ArgKind::Integer {
+                min: quote!(i64::MIN),
+                max: quote!(i64::MAX),
+            },
+            "u8" | "u16" | "u32" => ArgKind::Integer {
+                min: quote!(0),
+                max: quote!(#ty::MAX as i64),
+            },
+            "u64" => ArgKind::Integer {
+                min: quote!(0),
+                max: quote!(i64::MAX),
+            },
+            "f32" => ArgKind::Decimal {
+                min: quote!(f32::MIN as f64),
+                max: quote!(f32::MAX as f64),
+            },
+            "f64" => ArgKind::Decimal {
+                min: quote!(f64::MIN),
+                max: quote!(f64::MAX),
+            },
+            "bool" => ArgKind::Boolean,
+            "UserId" => ArgKind::User,
+            "TimestampMillis" => ArgKind::DateTime,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "unsupported arg type, expected one of `String`, `bool`, `UserId`, \
+                    `TimestampMillis` or a primitive integer or float type",
+                ))
+            }
+        };
+
+        Ok(kind)
+    }
+
+    fn validate(&self, attrs: &ArgAttrs, field: &Field) -> syn::Result<()> {
+        let is_string = matches!(self, ArgKind::String);
+        let is_number = matches!(self, ArgKind::Integer { .. } | ArgKind::Decimal { .. });
+
+        let invalid = if !is_string && (attrs.min_length.is_some() || attrs.max_length.is_some()) {
+            Some("`min_length` and `max_length` only apply to `String` args")
+        } else if !is_string && attrs.multi_line {
+            Some("`multi_line` only applies to `String` args")
+        } else if !is_number && (attrs.min_value.is_some() || attrs.max_value.is_some()) {
+            Some("`min_value` and `max_value` only apply to integer and decimal args")
+        } else if attrs.autocomplete && !matches!(self, ArgKind::String | ArgKind::Integer { .. }) {
+            Some("`autocomplete` only applies to `String` and integer args")
+        } else if !is_string && !is_number && !attrs.choices.is_empty() {
+            Some("`choice` only applies to `String`, integer and decimal args")
+        } else {
+            None
+        };
+
+        match invalid {
+            Some(message) => Err(syn::Error::new_spanned(field, message)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ArgAttrs {
+    fn parse(field: &Field) -> syn::Result<ArgAttrs> {
+        let mut attrs = ArgAttrs::default();
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("arg"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    attrs.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("description") {
+                    attrs.description = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("placeholder") {
+                    attrs.placeholder = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("min_length") {
+                    attrs.min_length = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("max_length") {
+                    attrs.max_length = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("min_value") {
+                    attrs.min_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("max_value") {
+                    attrs.max_value = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("multi_line") {
+                    attrs.multi_line = true;
+                } else if meta.path.is_ident("autocomplete") {
+                    attrs.autocomplete = true;
+                } else if meta.path.is_ident("datetime") {
+                    attrs.datetime = true;
+                } else if meta.path.is_ident("future_only") {
+                    attrs.future_only = true;
+                } else if meta.path.is_ident("choice") {
+                    attrs.choices.push(parse_choice(&meta)?);
+                } else {
+                    return Err(meta.error("unsupported arg attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(attrs)
+    }
+}
+
+fn parse_choice(meta: &ParseNestedMeta) -> syn::Result<(LitStr, Expr)> {
+    let mut name = None;
+    let mut value = None;
+
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("name") {
+            name = Some(inner.value()?.parse()?);
+        } else if inner.path.is_ident("value") {
+            value = Some(inner.value()?.parse()?);
+        } else {
+            return Err(inner.error("expected `name` or `value`"));
+        }
+        Ok(())
+    })?;
+
+    match (name, value) {
+        (Some(name), Some(value)) => Ok((name, value)),
+        _ => Err(meta.error("a choice requires both a `name` and a `value`")),
+    }
+}
+
+fn choice(name: &LitStr, value: TokenStream) -> TokenStream {
+    quote! {
+        ::oc_bots_sdk::api::definition::BotCommandOptionChoice {
+            name: #name.to_string(),
+            value: #value,
+        }
+    }
+}
+
+fn optional_string(value: Option<&LitStr>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::std::option::Option::Some(#value.to_string())),
+        None => quote!(::std::option::Option::None),
+    }
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+fn type_name(ty: &Type) -> Option<String> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if !segment.arguments.is_empty() {
+        return None;
+    }
+    Some(segment.ident.to_string())
+}
@@ -0,0 +1,44 @@
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod command_args;
+
+/// Derives `oc_bots_sdk::api::command::CommandArgs` for a struct with named fields.
+///
+/// Each field becomes a `BotCommandParam` named after the field and the struct gets a fallible
+/// `TryFrom<&Command>` implementation which extracts the arguments from a command. The parameter
+/// type is inferred from the field type:
+///
+/// - `String` => `StringParam`
+/// - `i8`, `i16`, `i32`, `i64`, `u8`, `u16`, `u32`, `u64` => `IntegerParam`
+/// - `f32`, `f64` => `DecimalParam`
+/// - `bool` => `BooleanParam`
+/// - `UserId` => `UserParam`
+/// - `TimestampMillis` => `DateTimeParam`
+///
+/// Wrapping the type in an `Option` makes the parameter optional.
+///
+/// Fields can be further described with the `#[arg(...)]` attribute:
+///
+/// ```ignore
+/// #[derive(CommandArgs)]
+/// struct RollArgs {
+///     #[arg(description = "The number of sides on each die", min_value = 1, max_value = 100)]
+///     sides: Option<u32>,
+///     #[arg(name = "count", placeholder = "1", choice(name = "One", value = 1), choice(name = "Two", value = 2))]
+///     dice: Option<u32>,
+/// }
+/// ```
+///
+/// The supported keys are `name`, `description`, `placeholder`, `min_length`, `max_length`,
+/// `multi_line` (strings), `min_value`, `max_value` (integers and decimals), `choice` (strings,
+/// integers and decimals), `autocomplete` (strings and integers), `datetime` and `future_only`
+/// (datetimes).
+#[proc_macro_derive(CommandArgs, attributes(arg))]
+pub fn derive_command_args(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    command_args::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
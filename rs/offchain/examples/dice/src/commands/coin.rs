@@ -57,11 +57,13 @@ impl Coin {
                     min_value: 1,
                     max_value: 10,
                     choices: Vec::new(),
+                    autocomplete: false,
                 }),
             }],
             permissions: BotPermissions::text_only(),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
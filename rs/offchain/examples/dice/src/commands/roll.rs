@@ -1,25 +1,29 @@
 use async_trait::async_trait;
-use oc_bots_sdk::api::command::{CommandHandler, SuccessResult};
+use oc_bots_sdk::api::command::{
+    AutocompleteArgs, AutocompleteChoice, CommandArgValue, CommandHandler, SuccessResult,
+};
 use oc_bots_sdk::api::definition::*;
 use oc_bots_sdk::oc_api::client::Client;
+use oc_bots_sdk::oc_api::Runtime;
 use oc_bots_sdk::types::BotCommandContext;
-use oc_bots_sdk_offchain::AgentRuntime;
 use rand::{thread_rng, Rng};
 use std::sync::LazyLock;
 
 static DEFINITION: LazyLock<BotCommandDefinition> = LazyLock::new(Roll::definition);
 
+const COMMON_DICE: [i64; 7] = [4, 6, 8, 10, 12, 20, 100];
+
 pub struct Roll;
 
 #[async_trait]
-impl CommandHandler<AgentRuntime> for Roll {
+impl<R: Runtime> CommandHandler<R> for Roll {
     fn definition(&self) -> &BotCommandDefinition {
         &DEFINITION
     }
 
     async fn execute(
         &self,
-        oc_client: Client<AgentRuntime, BotCommandContext>,
+        oc_client: Client<R, BotCommandContext>,
     ) -> Result<SuccessResult, String> {
         let cxt = oc_client.context();
         let sides = cxt.command.maybe_arg("sides").unwrap_or(6);
@@ -41,6 +45,23 @@ impl CommandHandler<AgentRuntime> for Roll {
 
         Ok(SuccessResult { message })
     }
+
+    async fn autocomplete(
+        &self,
+        _oc_client: Client<R, BotCommandContext>,
+        args: AutocompleteArgs,
+    ) -> Result<Vec<AutocompleteChoice>, String> {
+        let input = args.input.trim();
+
+        Ok(COMMON_DICE
+            .iter()
+            .filter(|sides| sides.to_string().starts_with(input))
+            .map(|sides| AutocompleteChoice {
+                name: format!("d{sides}"),
+                value: CommandArgValue::Integer(*sides),
+            })
+            .collect())
+    }
 }
 
 impl Roll {
@@ -59,6 +80,7 @@ impl Roll {
                         min_value: 1,
                         max_value: 1_000_000_000,
                         choices: Vec::new(),
+                        autocomplete: true,
                     }),
                 },
                 BotCommandParam {
@@ -70,12 +92,81 @@ impl Roll {
                         min_value: 1,
                         max_value: 10,
                         choices: Vec::new(),
+                        autocomplete: false,
                     }),
                 },
             ],
             permissions: BotPermissions::text_only(),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oc_bots_sdk::api::command::{Command, CommandArg};
+    use oc_bots_sdk::oc_api::actions::send_message::{self, SendMessageAction};
+    use oc_bots_sdk::testing::MockRuntime;
+    use oc_bots_sdk::types::{
+        AuthToken, BotActionChatDetails, BotCommandScope, CanisterId, Chat, MessageContentInitial,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn roll_sends_message() {
+        let runtime = Arc::new(MockRuntime::new());
+        runtime.respond_with::<SendMessageAction>(send_message::Response::Success(
+            send_message::SuccessResult {
+                message_id: 1.into(),
+                event_index: 1,
+                message_index: 1,
+                timestamp: 0,
+                expires_at: None,
+            },
+        ));
+
+        // Rolling one sided dice gives a predictable result
+        let context = BotCommandContext {
+            token: AuthToken::Jwt(String::new()),
+            bot_id: CanisterId::anonymous().into(),
+            api_gateway: CanisterId::anonymous(),
+            command: Command {
+                name: "roll".to_string(),
+                args: vec![
+                    CommandArg {
+                        name: "sides".to_string(),
+                        value: CommandArgValue::Integer(1),
+                    },
+                    CommandArg {
+                        name: "count".to_string(),
+                        value: CommandArgValue::Integer(3),
+                    },
+                ],
+                initiator: CanisterId::anonymous().into(),
+                meta: None,
+            },
+            scope: BotCommandScope::Chat(BotActionChatDetails {
+                chat: Chat::Group(CanisterId::anonymous()),
+                thread: None,
+                message_id: 1.into(),
+                user_message_id: None,
+            }),
+            granted_permissions: BotPermissions::text_only(),
+        };
+
+        let client = Client::new(runtime.clone(), context);
+        let result = runtime.block_on(Roll.execute(client)).unwrap();
+
+        assert!(result.message.is_some());
+
+        let sent = runtime.calls_to::<SendMessageAction>();
+        assert_eq!(sent.len(), 1);
+        assert!(matches!(
+            &sent[0].content,
+            MessageContentInitial::Text(content) if content.text == "1\n1\n1"
+        ));
+    }
+}
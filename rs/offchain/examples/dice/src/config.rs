@@ -1,4 +1,5 @@
-use oc_bots_sdk::mainnet::{mainnet_ic_url, mainnet_oc_public_key};
+use oc_bots_sdk::mainnet::{mainnet_ic_url, mainnet_oc_trusted_keys};
+use oc_bots_sdk::TrustedKeys;
 use serde::Deserialize;
 use std::error::Error;
 use std::fs;
@@ -8,8 +9,10 @@ pub struct Config {
     pub pem_file: String,
     #[serde(default = "mainnet_ic_url")]
     pub ic_url: String,
-    #[serde(default = "mainnet_oc_public_key")]
-    pub oc_public_key: String,
+    // Either a single PEM or a list of keys, eg. the current and next key while OpenChat's key is
+    // being rotated
+    #[serde(default = "mainnet_oc_trusted_keys")]
+    pub oc_public_key: TrustedKeys,
     pub port: u16,
 }
 
@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use oc_bots_sdk::api::event::BotEventHandler;
+use oc_bots_sdk::oc_api::actions::ActionArgsBuilder;
+use oc_bots_sdk::oc_api::client::Client;
+use oc_bots_sdk::types::{BotEventContext, ChatEvent, ChatEventType};
+use oc_bots_sdk_offchain::AgentRuntime;
+
+pub struct Welcome;
+
+#[async_trait]
+impl BotEventHandler<AgentRuntime> for Welcome {
+    fn subscriptions(&self) -> &[ChatEventType] {
+        &[ChatEventType::MembershipUpdate]
+    }
+
+    async fn handle(&self, oc_client: Client<AgentRuntime, BotEventContext>) -> Result<(), String> {
+        let ChatEvent::ParticipantJoined(joined) = &oc_client.context().event.event else {
+            return Ok(());
+        };
+
+        let text = format!(
+            "Welcome @UserId({})! Use /roll to roll some dice or /coin to toss a coin",
+            joined.user_id
+        );
+
+        // Send the message to OpenChat but don't wait for the response
+        oc_client.send_text_message(text).execute(|_, _| ());
+
+        Ok(())
+    }
+}
@@ -7,9 +7,14 @@ use axum::routing::{get, post};
 use axum::{Extension, Router};
 use commands::coin::Coin;
 use commands::roll::Roll;
-use oc_bots_sdk::api::command::{CommandHandlerRegistry, CommandResponse};
-use oc_bots_sdk::api::definition::BotDefinition;
+use events::welcome::Welcome;
+use oc_bots_sdk::api::command::{
+    AutocompleteArgs, AutocompleteResponse, CommandHandlerRegistry, CommandResponse,
+};
+use oc_bots_sdk::api::definition::{AutonomousConfig, BotDefinition, BotPermissions};
+use oc_bots_sdk::api::event::{BotEventHandlerRegistry, NotifyResponse};
 use oc_bots_sdk::oc_api::client::ClientFactory;
+use oc_bots_sdk::TrustedKeys;
 use oc_bots_sdk_offchain::env;
 use oc_bots_sdk_offchain::middleware::tower::{ExtractJwtLayer, OpenChatJwt};
 use oc_bots_sdk_offchain::AgentRuntime;
@@ -20,6 +25,7 @@ use tracing::info;
 
 mod commands;
 mod config;
+mod events;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -51,14 +57,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .register(Coin)
         .register(Roll);
 
+    let events = BotEventHandlerRegistry::new(oc_client_factory.clone()).register(Welcome);
+
     let app_state = AppState {
         oc_client_factory,
         oc_public_key,
         commands,
+        events,
     };
 
     let routes = Router::new()
         .route("/execute_command", post(execute_command))
+        .route("/autocomplete", post(autocomplete))
+        .route("/notify", post(notify))
         .route_layer(ExtractJwtLayer::new())
         .fallback(get(bot_definition))
         .layer(CorsLayer::permissive())
@@ -97,17 +108,67 @@ async fn execute_command(
     }
 }
 
+async fn autocomplete(
+    State(state): State<Arc<AppState>>,
+    Extension(OpenChatJwt(jwt)): Extension<OpenChatJwt>,
+    Json(args): Json<AutocompleteArgs>,
+) -> (StatusCode, Bytes) {
+    match state
+        .commands
+        .autocomplete(&jwt, &state.oc_public_key, env::now(), args)
+        .await
+    {
+        AutocompleteResponse::Success(r) => {
+            (StatusCode::OK, Bytes::from(serde_json::to_vec(&r).unwrap()))
+        }
+        AutocompleteResponse::BadRequest(r) => (
+            StatusCode::BAD_REQUEST,
+            Bytes::from(serde_json::to_vec(&r).unwrap()),
+        ),
+        AutocompleteResponse::InternalError(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Bytes::from(serde_json::to_vec(&err).unwrap()),
+        ),
+    }
+}
+
+async fn notify(
+    State(state): State<Arc<AppState>>,
+    Extension(OpenChatJwt(jwt)): Extension<OpenChatJwt>,
+) -> (StatusCode, Bytes) {
+    match state
+        .events
+        .notify(&jwt, &state.oc_public_key, env::now())
+        .await
+    {
+        NotifyResponse::Success => (StatusCode::OK, Bytes::new()),
+        NotifyResponse::BadRequest(r) => (
+            StatusCode::BAD_REQUEST,
+            Bytes::from(serde_json::to_vec(&r).unwrap()),
+        ),
+        NotifyResponse::InternalError(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Bytes::from(serde_json::to_vec(&err).unwrap()),
+        ),
+    }
+}
+
 async fn bot_definition(State(state): State<Arc<AppState>>, _body: String) -> Json<BotDefinition> {
     Json(BotDefinition {
         description: "Use this bot to roll dice or toss coins".to_string(),
         commands: state.commands.definitions(),
-        autonomous_config: None,
+        autonomous_config: Some(AutonomousConfig {
+            permissions: BotPermissions::text_only(),
+            sync_api_key: false,
+            subscriptions: state.events.subscriptions(),
+        }),
     })
 }
 
 struct AppState {
     #[allow(dead_code)]
     oc_client_factory: Arc<ClientFactory<AgentRuntime>>,
-    oc_public_key: String,
+    oc_public_key: TrustedKeys,
     commands: CommandHandlerRegistry<AgentRuntime>,
+    events: BotEventHandlerRegistry<AgentRuntime>,
 }
@@ -1,5 +1,6 @@
 use crate::errors::BotError;
-use oc_bots_sdk::mainnet::{mainnet_ic_url, mainnet_oc_public_key};
+use oc_bots_sdk::mainnet::{mainnet_ic_url, mainnet_oc_trusted_keys};
+use oc_bots_sdk::TrustedKeys;
 use serde::de::Deserializer;
 use serde::Deserialize;
 use serde_valid::validation::Error as ValidError;
@@ -29,8 +30,10 @@ pub struct DiscordConfig {
 pub struct OpenChatConfig {
     #[serde(default = "mainnet_ic_url")]
     pub ic_url: String,
-    #[serde(default = "mainnet_oc_public_key")]
-    pub public_key: String,
+    // Either a single PEM or a list of keys, eg. the current and next key while OpenChat's key is
+    // being rotated
+    #[serde(default = "mainnet_oc_trusted_keys")]
+    pub public_key: TrustedKeys,
     pub bot: OpenChatBotConfig,
 }
 
@@ -88,22 +91,25 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use oc_bots_sdk::mainnet::OC_PUBLIC_KEY;
     use tracing::Level;
 
     #[test]
     fn minimal_config_is_parsed_correctly() -> Result<(), Box<dyn std::error::Error>> {
-        let config = "
+        let config = &format!(
+            "
             [discord]
             token = \"not-a-real-token\"
             [openchat]
             ic_url = \"http://localhost:8080\"
-            public_key = \"not-a-real-public-key\"
+            public_key = '''{OC_PUBLIC_KEY}'''
             [openchat.bot]
             port = 13456
             private_key_path = \"nothing/to/see/here\"
             [system]
             log_level = \"info\"
-        ";
+        "
+        );
 
         let parsed: Config = toml::from_str(config)?;
 
@@ -111,7 +117,7 @@ mod tests {
         assert_eq!(parsed.openchat.ic_url, "http://localhost:8080".to_string());
         assert_eq!(
             parsed.openchat.public_key,
-            "not-a-real-public-key".to_string()
+            TrustedKeys::from_pem(OC_PUBLIC_KEY).unwrap()
         );
         assert_eq!(parsed.openchat.bot.port, 13456);
         assert_eq!(
@@ -127,12 +133,13 @@ mod tests {
 
     #[test]
     fn full_config_is_parsed_correctly() -> Result<(), Box<dyn std::error::Error>> {
-        let config = "
+        let config = &format!(
+            "
             [discord]
             token = \"not-a-real-token\"
             [openchat]
             ic_url = \"http://localhost:8080\"
-            public_key = \"not-a-real-public-key\"
+            public_key = '''{OC_PUBLIC_KEY}'''
             [openchat.bot]
             port = 13456
             private_key_path = \"nothing/to/see/here\"
@@ -140,7 +147,8 @@ mod tests {
             log_level = \"info\"
             store_path = \"path/to/store\"
             store_encryption_key = \"-this-is-a-valid-32-bit-enc-key-\"
-        ";
+        "
+        );
 
         let parsed: Config = toml::from_str(config)?;
 
@@ -155,12 +163,13 @@ mod tests {
 
     #[test]
     fn enc_key_is_correctly_validated() -> Result<(), Box<dyn std::error::Error>> {
-        let config = "
+        let config = &format!(
+            "
             [discord]
             token = \"not-a-real-token\"
             [openchat]
             ic_url = \"http://localhost:8080\"
-            public_key = \"not-a-real-public-key\"
+            public_key = '''{OC_PUBLIC_KEY}'''
             [openchat.bot]
             port = 13456
             private_key_path = \"nothing/to/see/here\"
@@ -168,7 +177,8 @@ mod tests {
             log_level = \"info\"
             store_path = \"path/to/store\"
             store_encryption_key = \"-this-is-a-valid-32-bit-enc-key-\"
-        ";
+        "
+        );
 
         let parsed: Config = toml::from_str(config)?;
 
@@ -179,12 +189,13 @@ mod tests {
 
     #[test]
     fn enc_key_fails_validation() -> Result<(), Box<dyn std::error::Error>> {
-        let config_with_invalid_enc_key = "
+        let config_with_invalid_enc_key = &format!(
+            "
             [discord]
             token = \"not-a-real-token\"
             [openchat]
             ic_url = \"http://localhost:8080\"
-            public_key = \"not-a-real-public-key\"
+            public_key = '''{OC_PUBLIC_KEY}'''
             [openchat.bot]
             port = 13456
             private_key_path = \"nothing/to/see/here\"
@@ -192,7 +203,8 @@ mod tests {
             log_level = \"info\"
             store_path = \"path/to/store\"
             store_encryption_key = \"-this-is-definitelly-not-a-valid-32-bit-enc-key-\"
-        ";
+        "
+        );
 
         let parsed: Config = toml::from_str(config_with_invalid_enc_key)?;
 
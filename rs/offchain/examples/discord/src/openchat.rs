@@ -149,6 +149,7 @@ async fn bot_definition(State(oc_data): State<Arc<OcData>>) -> Json<BotDefinitio
         autonomous_config: Some(AutonomousConfig {
             permissions: BotPermissions::text_only(),
             sync_api_key: false,
+            subscriptions: vec![],
         }),
     })
 }
@@ -69,6 +69,7 @@ impl Status {
             permissions: BotPermissions::text_only(),
             default_role: None,
             direct_messages: None,
+            subcommands: vec![],
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::errors::BotError;
 use crate::openchat::OcData;
-use oc_bots_sdk::oc_api::actions::ActionArgsBuilder;
+use oc_bots_sdk::oc_api::actions::{ActionArgsBuilder, ActionResponse};
 use oc_bots_sdk::types::{BotApiKeyContext, MessageContentInitial, TextContent};
 use oc_bots_sdk_offchain::env;
 use poise::serenity_prelude::Message;
@@ -67,7 +67,8 @@ pub async fn handle_openchat_events(
                             .build(ctx)
                             .send_message(MessageContentInitial::Text(TextContent { text }))
                             .execute_async()
-                            .await;
+                            .await
+                            .and_then(|response| response.into_result());
 
                         let relay_link = if let Err(err) = res {
                             error!("Failed to send message to OC :: {:?}", err);
@@ -56,11 +56,13 @@ impl Prompt {
                     max_length: 10000,
                     choices: Vec::new(),
                     multi_line: true,
+                    autocomplete: false,
                 }),
             }],
             permissions: BotPermissions::from_message_permission(MessagePermission::Text),
             default_role: None,
             direct_messages: Some(true),
+            subcommands: vec![],
         }
     }
 }
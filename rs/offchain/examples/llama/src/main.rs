@@ -6,10 +6,11 @@ use axum::extract::State;
 use axum::http::StatusCode;
 use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
-use oc_bots_sdk::api::command::{CommandHandlerRegistry, CommandResponse};
+use oc_bots_sdk::api::command::{CommandHandlerRegistry, CommandResponse, RateLimiter};
 use oc_bots_sdk::api::definition::BotDefinition;
 use oc_bots_sdk::mainnet::IC_URL;
 use oc_bots_sdk::oc_api::client::ClientFactory;
+use oc_bots_sdk::TrustedKeys;
 use oc_bots_sdk_offchain::env;
 use oc_bots_sdk_offchain::middleware::tower::{ExtractJwtLayer, OpenChatJwt};
 use oc_bots_sdk_offchain::AgentRuntime;
@@ -50,8 +51,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Init Llama3 LLM canister agent
     let llama_agent = oc_bots_sdk_offchain::build_agent(IC_URL.to_string(), &config.pem_file).await;
 
+    // Each prompt results in a call to the LLM canister so limit how often users can send them
     let commands = CommandHandlerRegistry::new(oc_client_factory)
-        .register(Prompt::new(LlmCanisterAgent::new(llama_agent)));
+        .register(Prompt::new(LlmCanisterAgent::new(llama_agent)))
+        .with_interceptor(RateLimiter::new().per_user(5, 60_000).per_scope(20, 60_000));
 
     let app_state = AppState {
         oc_public_key: config.oc_public_key,
@@ -111,6 +114,6 @@ async fn bot_definition(State(state): State<Arc<AppState>>, _body: String) -> Js
 }
 
 struct AppState {
-    oc_public_key: String,
+    oc_public_key: TrustedKeys,
     commands: CommandHandlerRegistry<AgentRuntime>,
 }
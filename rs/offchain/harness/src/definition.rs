@@ -0,0 +1,67 @@
+use oc_bots_sdk::api::definition::{BotCommandDefinition, BotDefinition};
+use serde_json::Value;
+use std::error::Error;
+
+// The schema OpenChat validates bot definitions against when a bot is registered
+const BOT_SCHEMA: &str = include_str!("../../../../schema/bot_schema.json");
+
+pub async fn fetch(
+    http_client: &reqwest::Client,
+    url: &str,
+) -> Result<BotDefinition, Box<dyn Error + Send + Sync>> {
+    // Bots serve their definition from their fallback route
+    let definition: Value = http_client
+        .get(format!("{url}/bot_definition"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    validate(&definition)?;
+
+    Ok(serde_json::from_value(definition)?)
+}
+
+// Commands which have subcommands can't be executed directly so each subcommand is listed
+// instead, named as the bot expects them, eg. "remind at"
+pub fn commands(definitions: &[BotCommandDefinition]) -> Vec<(String, &BotCommandDefinition)> {
+    definitions
+        .iter()
+        .flat_map(|definition| {
+            if definition.subcommands.is_empty() {
+                vec![(definition.name.clone(), definition)]
+            } else {
+                definition
+                    .subcommands
+                    .iter()
+                    .map(|subcommand| {
+                        (
+                            format!("{} {}", definition.name, subcommand.name),
+                            subcommand,
+                        )
+                    })
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+fn validate(definition: &Value) -> Result<(), String> {
+    let schema: Value = serde_json::from_str(BOT_SCHEMA).expect("Invalid bot schema");
+    let validator = jsonschema::validator_for(&schema).map_err(|error| error.to_string())?;
+
+    let errors: Vec<_> = validator
+        .iter_errors(definition)
+        .map(|error| format!("  {}: {error}", error.instance_path))
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "The bot definition is not valid against the schema:\n{}",
+            errors.join("\n")
+        ))
+    }
+}
@@ -0,0 +1,123 @@
+// Everything here relies on the `TestTokenIssuer`, see the `testing` feature in Cargo.toml
+#![cfg(feature = "testing")]
+
+use candid::Principal;
+use clap::Parser;
+use oc_bots_sdk::api::command::{BadRequest, Command, CommandMeta, SuccessResult};
+use oc_bots_sdk::testing::TestTokenIssuer;
+use oc_bots_sdk::types::{BotActionChatDetails, BotCommandScope, Chat};
+use oc_bots_sdk_offchain::env;
+use reqwest::StatusCode;
+use std::error::Error;
+use std::path::Path;
+
+mod definition;
+mod prompt;
+
+// How long the tokens minted for each command are valid for
+const TOKEN_LIFETIME: u64 = 5 * 60 * 1000; // 5 minutes
+
+#[derive(Parser, Debug)]
+pub struct Config {
+    /// The bot's base URL, eg. http://localhost:4000 for an offchain bot or
+    /// http://<canister_id>.raw.localhost:8080 for a bot canister on a local replica
+    #[arg(long)]
+    url: String,
+
+    /// The PEM file holding the private key used to sign tokens, created if it doesn't exist.
+    /// The bot must be configured with the matching public key in place of OpenChat's.
+    #[arg(long, default_value = "harness_key.pem")]
+    key_file: String,
+
+    /// The user who initiates each command
+    #[arg(long, default_value_t = Principal::anonymous())]
+    initiator: Principal,
+
+    /// The group chat each command is executed in
+    #[arg(long, default_value_t = Principal::anonymous())]
+    chat: Principal,
+
+    /// The IANA timezone sent with each command
+    #[arg(long, default_value = "UTC")]
+    timezone: String,
+}
+
+pub async fn run(config: Config) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let issuer = load_or_create_issuer(&config.key_file)?;
+    println!(
+        "Tokens are signed using the key in '{}'. Configure the bot with this public key:\n\n{}",
+        config.key_file,
+        issuer.public_key_pem()
+    );
+
+    let url = config.url.trim_end_matches('/');
+    let http_client = reqwest::Client::new();
+
+    // Fetch the bot's definition and check it would be accepted by OpenChat
+    let definition = definition::fetch(&http_client, url).await?;
+    let commands = definition::commands(&definition.commands);
+
+    while let Some((name, command)) = prompt::command(&commands)? {
+        let args = prompt::args(&command.params)?;
+        let now = env::now();
+
+        let jwt = issuer.command_jwt(
+            Command {
+                name,
+                args,
+                initiator: config.initiator.into(),
+                meta: Some(CommandMeta {
+                    timezone: config.timezone.clone(),
+                    language: "en".to_string(),
+                }),
+            },
+            BotCommandScope::Chat(BotActionChatDetails {
+                chat: Chat::Group(config.chat),
+                thread: None,
+                message_id: rand::random::<u64>().into(),
+                user_message_id: None,
+            }),
+            command.permissions.clone(),
+            now + TOKEN_LIFETIME,
+        );
+
+        let response = http_client
+            .post(format!("{url}/execute_command"))
+            .header("x-oc-jwt", jwt)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        print_response(status, &body);
+    }
+
+    Ok(())
+}
+
+fn load_or_create_issuer(key_file: &str) -> Result<TestTokenIssuer, Box<dyn Error + Send + Sync>> {
+    if Path::new(key_file).exists() {
+        let private_key_pem = std::fs::read_to_string(key_file)?;
+        TestTokenIssuer::from_private_key_pem(&private_key_pem)
+            .map_err(|error| format!("Invalid key file '{key_file}': {error}").into())
+    } else {
+        let issuer = TestTokenIssuer::new();
+        std::fs::write(key_file, issuer.private_key_pem())?;
+        Ok(issuer)
+    }
+}
+
+fn print_response(status: StatusCode, body: &str) {
+    match status {
+        StatusCode::OK => match serde_json::from_str::<SuccessResult>(body) {
+            Ok(result) => println!("Success:\n{result:#?}"),
+            Err(error) => println!("Success but failed to decode the response: {error}\n{body}"),
+        },
+        StatusCode::BAD_REQUEST => match serde_json::from_str::<BadRequest>(body) {
+            Ok(bad_request) => println!("Bad request:\n{bad_request:#?}"),
+            Err(_) => println!("Bad request:\n{body}"),
+        },
+        _ => println!("{status}:\n{body}"),
+    }
+}
@@ -0,0 +1,14 @@
+use clap::Parser;
+use std::process;
+
+use oc_bots_harness::{run, Config};
+
+#[tokio::main]
+async fn main() {
+    let config = Config::parse();
+
+    if let Err(e) = run(config).await {
+        eprintln!("Application error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,247 @@
+use candid::Principal;
+use oc_bots_sdk::api::command::{CommandArg, CommandArgValue};
+use oc_bots_sdk::api::definition::{
+    BotCommandDefinition, BotCommandOptionChoice, BotCommandParam, BotCommandParamType,
+};
+use oc_bots_sdk::types::TimestampMillis;
+use oc_bots_sdk_offchain::env;
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+// Returns `None` once the user has finished
+pub fn command<'a>(
+    commands: &'a [(String, &'a BotCommandDefinition)],
+) -> io::Result<Option<(String, &'a BotCommandDefinition)>> {
+    println!("\nCommands:");
+    for (name, definition) in commands {
+        match &definition.description {
+            Some(description) => println!("  /{name} - {description}"),
+            None => println!("  /{name}"),
+        }
+    }
+
+    loop {
+        let input = read_line("\nCommand (leave empty to exit): ")?;
+        let name = input.trim_start_matches('/');
+
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        if let Some((name, definition)) = commands.iter().find(|(n, _)| n == name) {
+            return Ok(Some((name.clone(), *definition)));
+        }
+
+        println!("Command not found: {name}");
+    }
+}
+
+pub fn args(params: &[BotCommandParam]) -> io::Result<Vec<CommandArg>> {
+    let mut args = Vec::new();
+
+    for param in params {
+        loop {
+            let input = read_line(&describe(param))?;
+
+            if input.is_empty() && !param.required {
+                break;
+            }
+
+            match parse(&param.param_type, &input, env::now()) {
+                Ok(value) => {
+                    args.push(CommandArg {
+                        name: param.name.clone(),
+                        value,
+                    });
+                    break;
+                }
+                Err(error) => println!("  {error}"),
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+fn describe(param: &BotCommandParam) -> String {
+    let hint = match &param.param_type {
+        BotCommandParamType::BooleanParam => "true/false".to_string(),
+        BotCommandParamType::StringParam(p) if !p.choices.is_empty() => choices(&p.choices),
+        BotCommandParamType::StringParam(p) => format!("{}-{} chars", p.min_length, p.max_length),
+        BotCommandParamType::IntegerParam(p) if !p.choices.is_empty() => choices(&p.choices),
+        BotCommandParamType::IntegerParam(p) => format!("{} to {}", p.min_value, p.max_value),
+        BotCommandParamType::DecimalParam(p) if !p.choices.is_empty() => choices(&p.choices),
+        BotCommandParamType::DecimalParam(p) => format!("{} to {}", p.min_value, p.max_value),
+        BotCommandParamType::DateTimeParam(_) => "timestamp in ms".to_string(),
+        BotCommandParamType::UserParam => "user id".to_string(),
+    };
+
+    let mut prompt = format!("  {} [{hint}]", param.name);
+    if !param.required {
+        prompt.push_str(" (optional)");
+    }
+    if let Some(description) = &param.description {
+        prompt.push_str(&format!(" {description}"));
+    }
+    prompt.push_str(": ");
+    prompt
+}
+
+fn choices<T: Display>(choices: &[BotCommandOptionChoice<T>]) -> String {
+    choices
+        .iter()
+        .map(|choice| format!("{}={}", choice.name, choice.value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Checks the input is valid for the parameter in the same way as OpenChat does before it calls
+// the bot
+fn parse(
+    param_type: &BotCommandParamType,
+    input: &str,
+    now: TimestampMillis,
+) -> Result<CommandArgValue, String> {
+    match param_type {
+        BotCommandParamType::BooleanParam => match input.to_lowercase().as_str() {
+            "true" | "yes" | "y" => Ok(CommandArgValue::Boolean(true)),
+            "false" | "no" | "n" => Ok(CommandArgValue::Boolean(false)),
+            _ => Err("Expected true or false".to_string()),
+        },
+        BotCommandParamType::StringParam(p) => {
+            if !p.choices.is_empty() {
+                return find_choice(&p.choices, input, |value| value == input)
+                    .map(CommandArgValue::String);
+            }
+            let length = input.chars().count();
+            if length < p.min_length as usize || length > p.max_length as usize {
+                return Err(format!(
+                    "Must be between {} and {} characters",
+                    p.min_length, p.max_length
+                ));
+            }
+            Ok(CommandArgValue::String(input.to_string()))
+        }
+        BotCommandParamType::IntegerParam(p) => {
+            if !p.choices.is_empty() {
+                return find_choice(&p.choices, input, |value| {
+                    input.parse::<i64>().is_ok_and(|i| i == *value)
+                })
+                .map(CommandArgValue::Integer);
+            }
+            let value: i64 = input.parse().map_err(|_| "Expected an integer")?;
+            if value < p.min_value || value > p.max_value {
+                return Err(format!(
+                    "Must be between {} and {}",
+                    p.min_value, p.max_value
+                ));
+            }
+            Ok(CommandArgValue::Integer(value))
+        }
+        BotCommandParamType::DecimalParam(p) => {
+            if !p.choices.is_empty() {
+                return find_choice(&p.choices, input, |value| {
+                    input.parse::<f64>().is_ok_and(|d| d == *value)
+                })
+                .map(CommandArgValue::Decimal);
+            }
+            let value: f64 = input.parse().map_err(|_| "Expected a number")?;
+            if value < p.min_value || value > p.max_value {
+                return Err(format!(
+                    "Must be between {} and {}",
+                    p.min_value, p.max_value
+                ));
+            }
+            Ok(CommandArgValue::Decimal(value))
+        }
+        BotCommandParamType::DateTimeParam(p) => {
+            let value: TimestampMillis = input
+                .parse()
+                .map_err(|_| "Expected a timestamp in milliseconds")?;
+            if p.future_only && value <= now {
+                return Err("Must be in the future".to_string());
+            }
+            Ok(CommandArgValue::DateTime(value))
+        }
+        BotCommandParamType::UserParam => Principal::from_text(input)
+            .map(|principal| CommandArgValue::User(principal.into()))
+            .map_err(|error| format!("Invalid user id: {error}")),
+    }
+}
+
+// A choice can be selected either by its name or its value
+fn find_choice<T: Clone>(
+    choices: &[BotCommandOptionChoice<T>],
+    input: &str,
+    matches_value: impl Fn(&T) -> bool,
+) -> Result<T, String> {
+    choices
+        .iter()
+        .find(|choice| choice.name.eq_ignore_ascii_case(input) || matches_value(&choice.value))
+        .map(|choice| choice.value.clone())
+        .ok_or_else(|| "Not one of the available choices".to_string())
+}
+
+fn read_line(prompt: &str) -> io::Result<String> {
+    print!("{prompt}");
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if io::stdin().lock().read_line(&mut line)? == 0 {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok(line.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oc_bots_sdk::api::definition::IntegerParam;
+
+    #[test]
+    fn integer_args_are_checked() {
+        let param_type = BotCommandParamType::IntegerParam(IntegerParam {
+            min_value: 1,
+            max_value: 10,
+            choices: Vec::new(),
+            autocomplete: false,
+        });
+
+        assert!(matches!(
+            parse(&param_type, "5", 0),
+            Ok(CommandArgValue::Integer(5))
+        ));
+        assert!(parse(&param_type, "11", 0).is_err());
+        assert!(parse(&param_type, "five", 0).is_err());
+    }
+
+    #[test]
+    fn choices_are_matched_by_name_or_value() {
+        let param_type = BotCommandParamType::IntegerParam(IntegerParam {
+            min_value: 0,
+            max_value: 100,
+            choices: vec![
+                BotCommandOptionChoice {
+                    name: "One".to_string(),
+                    value: 1,
+                },
+                BotCommandOptionChoice {
+                    name: "Two".to_string(),
+                    value: 2,
+                },
+            ],
+            autocomplete: false,
+        });
+
+        assert!(matches!(
+            parse(&param_type, "two", 0),
+            Ok(CommandArgValue::Integer(2))
+        ));
+        assert!(matches!(
+            parse(&param_type, "1", 0),
+            Ok(CommandArgValue::Integer(1))
+        ));
+        assert!(parse(&param_type, "3", 0).is_err());
+    }
+}
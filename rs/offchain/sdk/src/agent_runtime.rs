@@ -1,9 +1,9 @@
 use candid::utils::{ArgumentDecoder, ArgumentEncoder};
-use ic_agent::Agent;
+use ic_agent::{Agent, AgentError};
 use oc_bots_sdk::oc_api::Runtime;
-use oc_bots_sdk::types::{CallResult, CanisterId, TimestampMillis};
+use oc_bots_sdk::types::{CallResult, CanisterId, OcApiError, TimestampMillis};
 use std::future::Future;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 pub struct AgentRuntime {
     agent: Agent,
@@ -23,15 +23,27 @@ impl Runtime for AgentRuntime {
         method_name: &str,
         args: A,
     ) -> CallResult<R> {
+        let args = candid::encode_args(args)
+            .map_err(|error| OcApiError::InvalidRequest(error.to_string()))?;
+
         match self
             .agent
             .update(&canister_id, method_name)
-            .with_arg(candid::encode_args(args).unwrap())
+            .with_arg(args)
             .call_and_wait()
             .await
         {
-            Ok(bytes) => Ok(candid::decode_args(&bytes).unwrap()),
-            Err(error) => Err((0, error.to_string())),
+            Ok(bytes) => {
+                candid::decode_args(&bytes).map_err(|error| OcApiError::Decode(error.to_string()))
+            }
+            Err(AgentError::CertifiedReject(reject) | AgentError::UncertifiedReject(reject)) => {
+                Err(OcApiError::Rejected {
+                    code: reject.reject_code as i32,
+                    message: reject.reject_message,
+                })
+            }
+            Err(AgentError::TimeoutWaitingForResponse()) => Err(OcApiError::Timeout),
+            Err(error) => Err(OcApiError::Transport(error.to_string())),
         }
     }
 
@@ -39,6 +51,10 @@ impl Runtime for AgentRuntime {
         self.runtime.spawn(f);
     }
 
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
+
     fn now(&self) -> TimestampMillis {
         SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)
@@ -1,10 +1,19 @@
+use crate::api::definition::BotCommandParam;
 use crate::types::{MessageContentInitial, MessageId, TimestampMillis, UserId};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
+pub use command_group::CommandGroup;
 pub use command_handler::{CommandHandler, CommandHandlerRegistry};
+pub use command_interceptor::CommandInterceptor;
+pub use oc_bots_sdk_macros::CommandArgs;
+pub use rate_limiter::{RateLimit, RateLimiter};
 
+mod command_group;
 mod command_handler;
+mod command_interceptor;
+mod rate_limiter;
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Command {
@@ -30,6 +39,28 @@ impl Command {
             .expect("Argument missing or unexpected type")
     }
 
+    pub fn try_maybe_arg<T: TryFrom<CommandArgValue>>(
+        &self,
+        name: &str,
+    ) -> Result<Option<T>, ArgError> {
+        let Some(arg) = self.args.iter().find(|arg| arg.name == name) else {
+            return Ok(None);
+        };
+
+        T::try_from(arg.value.clone())
+            .map(Some)
+            .map_err(|_| ArgError::new(name, ArgErrorReason::WrongType))
+    }
+
+    pub fn try_arg<T: TryFrom<CommandArgValue>>(&self, name: &str) -> Result<T, ArgError> {
+        self.try_maybe_arg(name)?
+            .ok_or_else(|| ArgError::new(name, ArgErrorReason::Missing))
+    }
+
+    pub fn parse_args<T: CommandArgs>(&self) -> Result<T, ArgError> {
+        T::try_from(self)
+    }
+
     pub fn timezone(&self) -> &str {
         self.meta
             .as_ref()
@@ -45,6 +76,76 @@ impl Command {
     }
 }
 
+/// A set of typed command arguments which can be parsed from a [`Command`].
+///
+/// This is usually implemented using `#[derive(CommandArgs)]` so that the params in the
+/// command definition and the code which parses the arguments are generated from the same struct.
+pub trait CommandArgs: for<'a> TryFrom<&'a Command, Error = ArgError> {
+    fn params() -> Vec<BotCommandParam>;
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ArgError {
+    pub param: String,
+    pub reason: ArgErrorReason,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum ArgErrorReason {
+    Missing,
+    WrongType,
+    TooShort(u16),
+    TooLong(u16),
+    IntegerOutOfRange(i64, i64),
+    DecimalOutOfRange(f64, f64),
+    NotInChoices,
+    InPast,
+    Unexpected,
+}
+
+impl ArgError {
+    pub fn new(param: &str, reason: ArgErrorReason) -> Self {
+        ArgError {
+            param: param.to_string(),
+            reason,
+        }
+    }
+}
+
+impl Display for ArgError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let param = &self.param;
+
+        match &self.reason {
+            ArgErrorReason::Missing => write!(f, "Argument '{param}' is missing"),
+            ArgErrorReason::WrongType => write!(f, "Argument '{param}' has an unexpected type"),
+            ArgErrorReason::TooShort(min_length) => write!(
+                f,
+                "Argument '{param}' must be at least {min_length} characters long"
+            ),
+            ArgErrorReason::TooLong(max_length) => write!(
+                f,
+                "Argument '{param}' must be at most {max_length} characters long"
+            ),
+            ArgErrorReason::IntegerOutOfRange(min_value, max_value) => write!(
+                f,
+                "Argument '{param}' must be between {min_value} and {max_value}"
+            ),
+            ArgErrorReason::DecimalOutOfRange(min_value, max_value) => write!(
+                f,
+                "Argument '{param}' must be between {min_value} and {max_value}"
+            ),
+            ArgErrorReason::NotInChoices => {
+                write!(f, "Argument '{param}' must be one of the available choices")
+            }
+            ArgErrorReason::InPast => write!(f, "Argument '{param}' must be in the future"),
+            ArgErrorReason::Unexpected => write!(f, "Argument '{param}' is not expected"),
+        }
+    }
+}
+
+impl std::error::Error for ArgError {}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct CommandArg {
     pub name: String,
@@ -251,6 +352,30 @@ pub struct SuccessResult {
     pub message: Option<Message>,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AutocompleteArgs {
+    pub param: String,
+    pub input: String,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum AutocompleteResponse {
+    Success(AutocompleteResult),
+    BadRequest(BadRequest),
+    InternalError(InternalError),
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AutocompleteResult {
+    pub choices: Vec<AutocompleteChoice>,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct AutocompleteChoice {
+    pub name: String,
+    pub value: CommandArgValue,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct Message {
     pub(crate) id: MessageId,
@@ -358,3 +483,65 @@ impl From<Message> for SuccessResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::definition::BotCommandParamType;
+    use candid::Principal;
+
+    #[derive(CommandArgs)]
+    struct TestArgs {
+        #[arg(description = "The message", max_length = 100)]
+        message: String,
+        #[arg(name = "count", min_value = 1, max_value = 10)]
+        repeat: Option<u8>,
+    }
+
+    #[test]
+    fn derived_params_match_fields() {
+        let params = TestArgs::params();
+
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0].name, "message");
+        assert!(params[0].required);
+        assert!(matches!(
+            &params[0].param_type,
+            BotCommandParamType::StringParam(p) if p.max_length == 100
+        ));
+        assert_eq!(params[1].name, "count");
+        assert!(!params[1].required);
+        assert!(matches!(
+            &params[1].param_type,
+            BotCommandParamType::IntegerParam(p) if p.min_value == 1 && p.max_value == 10
+        ));
+    }
+
+    #[test]
+    fn derived_args_parse_from_command() {
+        let mut command = Command {
+            name: "test".to_string(),
+            args: vec![CommandArg {
+                name: "count".to_string(),
+                value: CommandArgValue::Integer(3),
+            }],
+            initiator: UserId::from(Principal::anonymous()),
+            meta: None,
+        };
+
+        let error = command.parse_args::<TestArgs>().err().unwrap();
+        assert_eq!(error, ArgError::new("message", ArgErrorReason::Missing));
+
+        command.args.push(CommandArg {
+            name: "message".to_string(),
+            value: CommandArgValue::Boolean(true),
+        });
+        let error = command.parse_args::<TestArgs>().err().unwrap();
+        assert_eq!(error, ArgError::new("message", ArgErrorReason::WrongType));
+
+        command.args[1].value = CommandArgValue::String("hello".to_string());
+        let args = command.parse_args::<TestArgs>().unwrap();
+        assert_eq!(args.message, "hello");
+        assert_eq!(args.repeat, Some(3));
+    }
+}
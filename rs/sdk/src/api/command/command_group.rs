@@ -0,0 +1,41 @@
+use crate::api::command::CommandHandler;
+use crate::api::definition::BotCommandDefinition;
+
+/// A group of commands which are invoked using a common prefix, e.g. `/remind at` and
+/// `/remind list`.
+///
+/// The group's definition describes the top level command, and the definitions of the registered
+/// handlers are added to it as subcommands.
+pub struct CommandGroup<R> {
+    definition: BotCommandDefinition,
+    commands: Vec<Box<dyn CommandHandler<R>>>,
+}
+
+impl<R> CommandGroup<R> {
+    pub fn new(definition: BotCommandDefinition) -> Self {
+        Self {
+            definition,
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn register<C: CommandHandler<R> + 'static>(mut self, command: C) -> Self {
+        self.definition
+            .subcommands
+            .push(command.definition().clone());
+        self.commands.push(Box::new(command));
+        self
+    }
+
+    pub fn definition(&self) -> &BotCommandDefinition {
+        &self.definition
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    pub(crate) fn into_parts(self) -> (BotCommandDefinition, Vec<Box<dyn CommandHandler<R>>>) {
+        (self.definition, self.commands)
+    }
+}
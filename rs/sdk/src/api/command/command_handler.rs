@@ -2,27 +2,35 @@ use crate::api::command::*;
 use crate::api::definition::{
     BotCommandDefinition, BotCommandParam, BotCommandParamType, StringParam,
 };
+use crate::jwt::JwtVerifier;
 use crate::oc_api::client::{Client, ClientFactory};
 use crate::oc_api::Runtime;
-use crate::types::{BotCommandContext, TimestampMillis, TokenError};
+use crate::types::{BotCommandContext, MessageContentInitial, TimestampMillis, TokenError};
 use async_trait::async_trait;
 use std::sync::LazyLock;
 use std::{collections::HashMap, sync::Arc};
 
 pub struct CommandHandlerRegistry<R> {
+    // Keyed by the full command path, e.g. "greet" or "remind at" for commands within a group
     commands: HashMap<String, Box<dyn CommandHandler<R>>>,
+    groups: Vec<BotCommandDefinition>,
     on_sync_api_key:
         Option<Box<dyn Fn(BotCommandContext) -> CommandResponse + Send + Sync + 'static>>,
+    interceptors: Vec<Box<dyn CommandInterceptor<R>>>,
     oc_client_factory: Arc<ClientFactory<R>>,
 }
 
 static SET_API_KEY_PARAMS: LazyLock<Vec<BotCommandParam>> = LazyLock::new(set_api_key_params);
 
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+
 impl<R: Runtime> CommandHandlerRegistry<R> {
     pub fn new(oc_client_factory: Arc<ClientFactory<R>>) -> CommandHandlerRegistry<R> {
         Self {
             commands: HashMap::new(),
+            groups: Vec::new(),
             on_sync_api_key: None,
+            interceptors: Vec::new(),
             oc_client_factory,
         }
     }
@@ -33,6 +41,18 @@ impl<R: Runtime> CommandHandlerRegistry<R> {
         self
     }
 
+    pub fn register_group(mut self, group: CommandGroup<R>) -> Self {
+        let (definition, commands) = group.into_parts();
+
+        for command in commands {
+            self.commands
+                .insert(command_path(&definition.name, command.name()), command);
+        }
+
+        self.groups.push(definition);
+        self
+    }
+
     pub fn on_sync_api_key(
         mut self,
         callback: Box<dyn Fn(BotCommandContext) -> CommandResponse + Send + Sync + 'static>,
@@ -41,38 +61,36 @@ impl<R: Runtime> CommandHandlerRegistry<R> {
         self
     }
 
+    pub fn with_interceptor<I: CommandInterceptor<R> + 'static>(mut self, interceptor: I) -> Self {
+        self.interceptors.push(Box::new(interceptor));
+        self
+    }
+
     pub fn definitions(&self) -> Vec<BotCommandDefinition> {
         self.commands
-            .values()
-            .map(|c| c.definition().clone())
+            .iter()
+            .filter(|(path, c)| path.as_str() == c.name())
+            .map(|(_, c)| c.definition().clone())
+            .chain(self.groups.iter().cloned())
             .collect()
     }
 
     pub async fn execute(
         &self,
         jwt: &str,
-        public_key: &str,
+        public_key: &(impl JwtVerifier + ?Sized),
         now: TimestampMillis,
     ) -> CommandResponse {
-        let context = match BotCommandContext::parse(jwt.to_string(), public_key, now) {
-            Ok(a) => a,
-            Err(bad_request) => {
-                return match bad_request {
-                    TokenError::Invalid(error) => {
-                        CommandResponse::BadRequest(BadRequest::AccessTokenInvalid(error))
-                    }
-                    TokenError::Expired => {
-                        CommandResponse::BadRequest(BadRequest::AccessTokenExpired)
-                    }
-                }
-            }
+        let context = match parse_context(jwt, public_key, now) {
+            Ok(context) => context,
+            Err(bad_request) => return CommandResponse::BadRequest(bad_request),
         };
 
         let command_name = context.command.name.as_str();
 
         if command_name == "sync_api_key" {
             if let Some(on_sync_api_key) = &self.on_sync_api_key {
-                if !check_args_internal(&context.command.args, &SET_API_KEY_PARAMS, now) {
+                if check_args_internal(&context.command.args, &SET_API_KEY_PARAMS, now).is_err() {
                     return CommandResponse::BadRequest(BadRequest::ArgsInvalid);
                 }
 
@@ -86,17 +104,60 @@ impl<R: Runtime> CommandHandlerRegistry<R> {
             return CommandResponse::BadRequest(BadRequest::CommandNotFound);
         };
 
-        if !command_handler.check_args(&context.command.args, now) {
-            return CommandResponse::BadRequest(BadRequest::ArgsInvalid);
+        if let Err(error) = command_handler.check_args(&context.command.args, now) {
+            return args_invalid(&context, error);
+        }
+
+        let oc_client = self.oc_client_factory.build(context);
+
+        if self.interceptors.is_empty() {
+            return command_response(command_handler.execute(oc_client).await);
+        }
+
+        for interceptor in self.interceptors.iter() {
+            if let Err(response) = interceptor.before(&oc_client).await {
+                return response;
+            }
+        }
+
+        let mut result = command_handler.execute(oc_client.clone()).await;
+
+        for interceptor in self.interceptors.iter().rev() {
+            result = interceptor.after(&oc_client, result).await;
         }
 
-        let result = command_handler
-            .execute(self.oc_client_factory.build(context))
-            .await;
+        command_response(result)
+    }
+
+    pub async fn autocomplete(
+        &self,
+        jwt: &str,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+        args: AutocompleteArgs,
+    ) -> AutocompleteResponse {
+        let context = match parse_context(jwt, public_key, now) {
+            Ok(context) => context,
+            Err(bad_request) => return AutocompleteResponse::BadRequest(bad_request),
+        };
+
+        let Some(command_handler) = self.get(&context.command.name) else {
+            return AutocompleteResponse::BadRequest(BadRequest::CommandNotFound);
+        };
+
+        if !supports_autocomplete(&command_handler.definition().params, &args.param) {
+            return AutocompleteResponse::BadRequest(BadRequest::ArgsInvalid);
+        }
 
-        match result {
-            Ok(success) => CommandResponse::Success(success),
-            Err(error) => CommandResponse::InternalError(InternalError::CommandError(error)),
+        match command_handler
+            .autocomplete(self.oc_client_factory.build(context), args)
+            .await
+        {
+            Ok(mut choices) => {
+                choices.truncate(MAX_AUTOCOMPLETE_CHOICES);
+                AutocompleteResponse::Success(AutocompleteResult { choices })
+            }
+            Err(error) => AutocompleteResponse::InternalError(InternalError::CommandError(error)),
         }
     }
 
@@ -118,104 +179,167 @@ pub trait CommandHandler<R>: Send + Sync {
         &self.definition().name
     }
 
-    fn check_args(&self, args: &[CommandArg], now: TimestampMillis) -> bool {
+    fn check_args(&self, args: &[CommandArg], now: TimestampMillis) -> Result<(), ArgError> {
         check_args_internal(args, &self.definition().params, now)
     }
+
+    // Returns suggestions for a param which has `autocomplete` enabled, ranked from best to worst
+    async fn autocomplete(
+        &self,
+        _oc_client: Client<R, BotCommandContext>,
+        _args: AutocompleteArgs,
+    ) -> Result<Vec<AutocompleteChoice>, String>
+    where
+        R: Runtime,
+    {
+        Ok(Vec::new())
+    }
+}
+
+fn parse_context(
+    jwt: &str,
+    public_key: &(impl JwtVerifier + ?Sized),
+    now: TimestampMillis,
+) -> Result<BotCommandContext, BadRequest> {
+    BotCommandContext::parse(jwt.to_string(), public_key, now).map_err(|error| match error {
+        TokenError::Invalid(error) => BadRequest::AccessTokenInvalid(error.to_string()),
+        TokenError::Expired => BadRequest::AccessTokenExpired,
+    })
+}
+
+fn command_response(result: Result<SuccessResult, String>) -> CommandResponse {
+    match result {
+        Ok(success) => CommandResponse::Success(success),
+        Err(error) => CommandResponse::InternalError(InternalError::CommandError(error)),
+    }
+}
+
+fn supports_autocomplete(params: &[BotCommandParam], name: &str) -> bool {
+    params
+        .iter()
+        .find(|p| p.name == name)
+        .is_some_and(|p| match &p.param_type {
+            BotCommandParamType::StringParam(p) => p.autocomplete,
+            BotCommandParamType::IntegerParam(p) => p.autocomplete,
+            _ => false,
+        })
+}
+
+fn command_path(group: &str, name: &str) -> String {
+    format!("{group} {name}")
+}
+
+// If the command was initiated from a message then the error is returned to the user as an
+// ephemeral message so they know which argument to fix, otherwise it is returned as a BadRequest
+fn args_invalid(context: &BotCommandContext, error: ArgError) -> CommandResponse {
+    match context.scope.message_id() {
+        Some(message_id) => CommandResponse::Success(
+            EphemeralMessageBuilder::new(
+                MessageContentInitial::from_text(error.to_string()),
+                message_id,
+            )
+            .build()
+            .into(),
+        ),
+        None => CommandResponse::BadRequest(BadRequest::ArgsInvalid),
+    }
 }
 
 fn check_args_internal(
     args: &[CommandArg],
     params: &[BotCommandParam],
     now: TimestampMillis,
-) -> bool {
+) -> Result<(), ArgError> {
     if args.len() > params.len() {
-        return false;
+        let unexpected = args
+            .iter()
+            .find(|a| !params.iter().any(|p| p.name == a.name))
+            .unwrap_or(&args[args.len() - 1]);
+
+        return Err(ArgError::new(&unexpected.name, ArgErrorReason::Unexpected));
     }
 
     for param in params.iter() {
         let Some(arg) = args.iter().find(|a| a.name == param.name) else {
             if param.required {
-                return false;
+                return Err(ArgError::new(&param.name, ArgErrorReason::Missing));
             }
 
             continue;
         };
 
+        let error = |reason| Err(ArgError::new(&param.name, reason));
+
         match &param.param_type {
             BotCommandParamType::StringParam(p) => {
                 let Some(value) = arg.value.as_string() else {
-                    return false;
+                    return error(ArgErrorReason::WrongType);
                 };
 
-                if value.len() < p.min_length as usize {
-                    return false;
+                // Lengths are in characters, as shown to the user, rather than bytes
+                let length = value.chars().count();
+
+                if length < p.min_length as usize {
+                    return error(ArgErrorReason::TooShort(p.min_length));
                 }
 
-                if value.len() > p.max_length as usize {
-                    return false;
+                if length > p.max_length as usize {
+                    return error(ArgErrorReason::TooLong(p.max_length));
                 }
 
                 if !p.choices.is_empty() && !p.choices.iter().any(|c| c.value == value) {
-                    return false;
+                    return error(ArgErrorReason::NotInChoices);
                 }
             }
             BotCommandParamType::IntegerParam(p) => {
                 let Some(value) = arg.value.as_integer() else {
-                    return false;
+                    return error(ArgErrorReason::WrongType);
                 };
 
-                if value < p.min_value {
-                    return false;
-                }
-
-                if value > p.max_value {
-                    return false;
+                if value < p.min_value || value > p.max_value {
+                    return error(ArgErrorReason::IntegerOutOfRange(p.min_value, p.max_value));
                 }
 
                 if !p.choices.is_empty() && !p.choices.iter().any(|c| c.value == value) {
-                    return false;
+                    return error(ArgErrorReason::NotInChoices);
                 }
             }
             BotCommandParamType::DecimalParam(p) => {
                 let Some(value) = arg.value.as_decimal() else {
-                    return false;
+                    return error(ArgErrorReason::WrongType);
                 };
 
-                if value < p.min_value {
-                    return false;
-                }
-
-                if value > p.max_value {
-                    return false;
+                if value < p.min_value || value > p.max_value {
+                    return error(ArgErrorReason::DecimalOutOfRange(p.min_value, p.max_value));
                 }
 
                 if !p.choices.is_empty() && !p.choices.iter().any(|c| c.value == value) {
-                    return false;
+                    return error(ArgErrorReason::NotInChoices);
                 }
             }
             BotCommandParamType::BooleanParam => {
                 if !matches!(arg.value, CommandArgValue::Boolean(_)) {
-                    return false;
+                    return error(ArgErrorReason::WrongType);
                 }
             }
             BotCommandParamType::UserParam => {
                 if !matches!(arg.value, CommandArgValue::User(_)) {
-                    return false;
+                    return error(ArgErrorReason::WrongType);
                 }
             }
             BotCommandParamType::DateTimeParam(p) => {
                 let Some(value) = arg.value.as_datetime() else {
-                    return false;
+                    return error(ArgErrorReason::WrongType);
                 };
 
                 if p.future_only && value < now {
-                    return false;
+                    return error(ArgErrorReason::InPast);
                 }
             }
         }
     }
 
-    true
+    Ok(())
 }
 
 fn set_api_key_params() -> Vec<BotCommandParam> {
@@ -229,6 +353,460 @@ fn set_api_key_params() -> Vec<BotCommandParam> {
             max_length: 1000,
             choices: vec![],
             multi_line: false,
+            autocomplete: false,
         }),
     }]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::definition::{DateTimeParam, IntegerParam};
+
+    fn arg(name: &str, value: CommandArgValue) -> CommandArg {
+        CommandArg {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn check_args_reports_failing_param() {
+        let params = vec![
+            BotCommandParam {
+                name: "count".to_string(),
+                description: None,
+                placeholder: None,
+                required: true,
+                param_type: BotCommandParamType::IntegerParam(IntegerParam {
+                    min_value: 1,
+                    max_value: 10,
+                    choices: vec![],
+                    autocomplete: false,
+                }),
+            },
+            BotCommandParam {
+                name: "when".to_string(),
+                description: None,
+                placeholder: None,
+                required: false,
+                param_type: BotCommandParamType::DateTimeParam(DateTimeParam { future_only: true }),
+            },
+        ];
+
+        assert_eq!(
+            check_args_internal(&[], &params, 100),
+            Err(ArgError::new("count", ArgErrorReason::Missing))
+        );
+        assert_eq!(
+            check_args_internal(&[arg("count", CommandArgValue::Integer(11))], &params, 100),
+            Err(ArgError::new(
+                "count",
+                ArgErrorReason::IntegerOutOfRange(1, 10)
+            ))
+        );
+        assert_eq!(
+            check_args_internal(
+                &[
+                    arg("count", CommandArgValue::Integer(5)),
+                    arg("when", CommandArgValue::DateTime(50))
+                ],
+                &params,
+                100
+            ),
+            Err(ArgError::new("when", ArgErrorReason::InPast))
+        );
+        assert_eq!(
+            check_args_internal(&[arg("count", CommandArgValue::Integer(5))], &params, 100),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn string_lengths_are_in_characters() {
+        let params = vec![BotCommandParam {
+            name: "text".to_string(),
+            description: None,
+            placeholder: None,
+            required: true,
+            param_type: BotCommandParamType::StringParam(StringParam {
+                min_length: 2,
+                max_length: 3,
+                choices: vec![],
+                multi_line: false,
+                autocomplete: false,
+            }),
+        }];
+
+        let text = |value: &str| [arg("text", CommandArgValue::String(value.to_string()))];
+
+        // 3 characters but 9 bytes
+        assert_eq!(check_args_internal(&text("日本語"), &params, 0), Ok(()));
+        assert_eq!(
+            check_args_internal(&text("日本語!"), &params, 0),
+            Err(ArgError::new("text", ArgErrorReason::TooLong(3)))
+        );
+        assert_eq!(
+            check_args_internal(&text("é"), &params, 0),
+            Err(ArgError::new("text", ArgErrorReason::TooShort(2)))
+        );
+    }
+
+    // These run commands end to end using the `MockRuntime` and `TestTokenIssuer`
+    mod registry {
+        use super::*;
+        use crate::testing::{MockRuntime, TestTokenIssuer};
+        use crate::types::{BotActionChatDetails, BotCommandScope, BotPermissions, Chat};
+        use candid::Principal;
+        use std::sync::Mutex;
+
+        const NOW: TimestampMillis = 1_000_000;
+
+        type Log = Arc<Mutex<Vec<String>>>;
+
+        struct Echo {
+            definition: BotCommandDefinition,
+            log: Log,
+        }
+
+        #[async_trait]
+        impl CommandHandler<MockRuntime> for Echo {
+            fn definition(&self) -> &BotCommandDefinition {
+                &self.definition
+            }
+
+            async fn execute(
+                &self,
+                _oc_client: Client<MockRuntime, BotCommandContext>,
+            ) -> Result<SuccessResult, String> {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("execute {}", self.name()));
+                Ok(SuccessResult { message: None })
+            }
+
+            async fn autocomplete(
+                &self,
+                _oc_client: Client<MockRuntime, BotCommandContext>,
+                args: AutocompleteArgs,
+            ) -> Result<Vec<AutocompleteChoice>, String> {
+                Ok((0..30)
+                    .map(|i| AutocompleteChoice {
+                        name: format!("{}{i}", args.input),
+                        value: CommandArgValue::String(format!("{}{i}", args.input)),
+                    })
+                    .collect())
+            }
+        }
+
+        struct Recorder {
+            name: &'static str,
+            reject: bool,
+            log: Log,
+        }
+
+        #[async_trait]
+        impl CommandInterceptor<MockRuntime> for Recorder {
+            async fn before(
+                &self,
+                _oc_client: &Client<MockRuntime, BotCommandContext>,
+            ) -> Result<(), CommandResponse> {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("before {}", self.name));
+
+                if self.reject {
+                    Err(CommandResponse::TooManyRequests)
+                } else {
+                    Ok(())
+                }
+            }
+
+            async fn after(
+                &self,
+                _oc_client: &Client<MockRuntime, BotCommandContext>,
+                result: Result<SuccessResult, String>,
+            ) -> Result<SuccessResult, String> {
+                self.log
+                    .lock()
+                    .unwrap()
+                    .push(format!("after {}", self.name));
+                result
+            }
+        }
+
+        fn definition(name: &str) -> BotCommandDefinition {
+            BotCommandDefinition {
+                name: name.to_string(),
+                description: None,
+                placeholder: None,
+                params: vec![BotCommandParam {
+                    name: "text".to_string(),
+                    description: None,
+                    placeholder: None,
+                    required: false,
+                    param_type: BotCommandParamType::StringParam(StringParam {
+                        min_length: 0,
+                        max_length: 10,
+                        choices: vec![],
+                        multi_line: false,
+                        autocomplete: false,
+                    }),
+                }],
+                permissions: BotPermissions::text_only(),
+                default_role: None,
+                direct_messages: None,
+                subcommands: Vec::new(),
+            }
+        }
+
+        fn echo(name: &str, log: &Log) -> Echo {
+            Echo {
+                definition: definition(name),
+                log: log.clone(),
+            }
+        }
+
+        fn recorder(name: &'static str, reject: bool, log: &Log) -> Recorder {
+            Recorder {
+                name,
+                reject,
+                log: log.clone(),
+            }
+        }
+
+        fn registry() -> CommandHandlerRegistry<MockRuntime> {
+            CommandHandlerRegistry::new(Arc::new(ClientFactory::new(MockRuntime::new())))
+        }
+
+        fn command_jwt(issuer: &TestTokenIssuer, name: &str, args: Vec<CommandArg>) -> String {
+            issuer.command_jwt(
+                Command {
+                    name: name.to_string(),
+                    args,
+                    initiator: Principal::anonymous().into(),
+                    meta: None,
+                },
+                BotCommandScope::Chat(BotActionChatDetails {
+                    chat: Chat::Group(Principal::anonymous()),
+                    thread: None,
+                    message_id: 1.into(),
+                    user_message_id: None,
+                }),
+                BotPermissions::text_only(),
+                NOW + 60_000,
+            )
+        }
+
+        fn execute(
+            registry: &CommandHandlerRegistry<MockRuntime>,
+            name: &str,
+            args: Vec<CommandArg>,
+        ) -> CommandResponse {
+            let issuer = TestTokenIssuer::new();
+            let jwt = command_jwt(&issuer, name, args);
+
+            MockRuntime::new().block_on(registry.execute(&jwt, issuer.public_key(), NOW))
+        }
+
+        fn autocomplete(
+            registry: &CommandHandlerRegistry<MockRuntime>,
+            name: &str,
+            param: &str,
+        ) -> AutocompleteResponse {
+            let issuer = TestTokenIssuer::new();
+            let jwt = command_jwt(&issuer, name, Vec::new());
+            let args = AutocompleteArgs {
+                param: param.to_string(),
+                input: "d".to_string(),
+            };
+
+            MockRuntime::new().block_on(registry.autocomplete(&jwt, issuer.public_key(), NOW, args))
+        }
+
+        fn ephemeral_text(response: CommandResponse) -> Option<String> {
+            let CommandResponse::Success(SuccessResult {
+                message: Some(message),
+            }) = response
+            else {
+                return None;
+            };
+
+            match message.content {
+                MessageContentInitial::Text(text) if message.ephemeral => Some(text.text),
+                _ => None,
+            }
+        }
+
+        fn take(log: &Log) -> Vec<String> {
+            std::mem::take(&mut *log.lock().unwrap())
+        }
+
+        #[test]
+        fn interceptors_run_around_the_command() {
+            let log = Log::default();
+            let registry = registry()
+                .register(echo("echo", &log))
+                .with_interceptor(recorder("a", false, &log))
+                .with_interceptor(recorder("b", false, &log));
+
+            assert!(matches!(
+                execute(&registry, "echo", Vec::new()),
+                CommandResponse::Success(_)
+            ));
+            assert_eq!(
+                take(&log),
+                ["before a", "before b", "execute echo", "after b", "after a"]
+            );
+        }
+
+        #[test]
+        fn rejecting_interceptor_short_circuits() {
+            let log = Log::default();
+            let registry = registry()
+                .register(echo("echo", &log))
+                .with_interceptor(recorder("a", false, &log))
+                .with_interceptor(recorder("b", true, &log))
+                .with_interceptor(recorder("c", false, &log));
+
+            assert!(matches!(
+                execute(&registry, "echo", Vec::new()),
+                CommandResponse::TooManyRequests
+            ));
+            assert_eq!(take(&log), ["before a", "before b"]);
+        }
+
+        #[test]
+        fn invalid_args_are_rejected_before_interceptors_run() {
+            let log = Log::default();
+            let registry = registry()
+                .register(echo("echo", &log))
+                .with_interceptor(recorder("a", false, &log));
+
+            let response = execute(
+                &registry,
+                "echo",
+                vec![arg("text", CommandArgValue::String("x".repeat(11)))],
+            );
+
+            assert_eq!(
+                ephemeral_text(response).as_deref(),
+                Some("Argument 'text' must be at most 10 characters long")
+            );
+            assert!(take(&log).is_empty());
+        }
+
+        #[test]
+        fn invalid_args_do_not_use_up_rate_limit() {
+            let log = Log::default();
+            let registry = registry()
+                .register(echo("echo", &log))
+                .with_interceptor(RateLimiter::new().per_user(1, 60_000));
+
+            assert!(ephemeral_text(execute(
+                &registry,
+                "echo",
+                vec![arg("text", CommandArgValue::String("x".repeat(11)))]
+            ))
+            .is_some());
+            assert!(matches!(
+                execute(&registry, "echo", Vec::new()),
+                CommandResponse::Success(_)
+            ));
+            assert!(matches!(
+                execute(&registry, "echo", Vec::new()),
+                CommandResponse::TooManyRequests
+            ));
+        }
+
+        fn remind_group(log: &Log) -> CommandGroup<MockRuntime> {
+            CommandGroup::new(definition("remind"))
+                .register(echo("at", log))
+                .register(echo("list", log))
+        }
+
+        #[test]
+        fn group_commands_are_dispatched_by_path() {
+            let log = Log::default();
+            let registry = registry()
+                .register(echo("echo", &log))
+                .register_group(remind_group(&log));
+
+            assert!(matches!(
+                execute(&registry, "remind at", Vec::new()),
+                CommandResponse::Success(_)
+            ));
+            assert_eq!(take(&log), ["execute at"]);
+
+            // Neither the group nor its subcommands can be executed on their own
+            for name in ["remind", "at"] {
+                assert!(matches!(
+                    execute(&registry, name, Vec::new()),
+                    CommandResponse::BadRequest(BadRequest::CommandNotFound)
+                ));
+            }
+            assert!(take(&log).is_empty());
+        }
+
+        #[test]
+        fn definitions_include_groups_but_not_their_subcommands() {
+            let log = Log::default();
+            let registry = registry()
+                .register(echo("echo", &log))
+                .register_group(remind_group(&log));
+
+            let mut definitions: Vec<_> = registry
+                .definitions()
+                .into_iter()
+                .map(|d| (d.name, d.subcommands.len()))
+                .collect();
+            definitions.sort();
+
+            assert_eq!(
+                definitions,
+                [("echo".to_string(), 0), ("remind".to_string(), 2)]
+            );
+        }
+
+        #[test]
+        fn autocomplete_is_routed_to_params_which_enable_it() {
+            let log = Log::default();
+            let mut suggest = echo("suggest", &log);
+            if let BotCommandParamType::StringParam(p) =
+                &mut suggest.definition.params[0].param_type
+            {
+                p.autocomplete = true;
+            }
+            let registry = registry()
+                .register(echo("echo", &log))
+                .register(suggest)
+                .with_interceptor(recorder("a", true, &log));
+
+            // The handler suggests 30 choices, which are cut down to the most OpenChat will show
+            let AutocompleteResponse::Success(result) = autocomplete(&registry, "suggest", "text")
+            else {
+                panic!("Expected autocomplete to succeed");
+            };
+            assert_eq!(result.choices.len(), MAX_AUTOCOMPLETE_CHOICES);
+            assert_eq!(result.choices[0].name, "d0");
+
+            assert!(matches!(
+                autocomplete(&registry, "echo", "text"),
+                AutocompleteResponse::BadRequest(BadRequest::ArgsInvalid)
+            ));
+            assert!(matches!(
+                autocomplete(&registry, "suggest", "other"),
+                AutocompleteResponse::BadRequest(BadRequest::ArgsInvalid)
+            ));
+            assert!(matches!(
+                autocomplete(&registry, "missing", "text"),
+                AutocompleteResponse::BadRequest(BadRequest::CommandNotFound)
+            ));
+
+            // Interceptors don't run for autocomplete, so the rejecting one had no effect
+            assert!(take(&log).is_empty());
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::api::command::{CommandResponse, SuccessResult};
+use crate::oc_api::client::Client;
+use crate::oc_api::Runtime;
+use crate::types::BotCommandContext;
+use async_trait::async_trait;
+
+/// Hooks which run around every command handled by a [`super::CommandHandlerRegistry`].
+///
+/// Interceptors are run in the order they were added before the command handler is called and in
+/// the reverse order afterwards. Returning an `Err` from [`CommandInterceptor::before`]
+/// short-circuits the chain and the given [`CommandResponse`] is returned without the command
+/// being executed. Interceptors only run for commands whose args are valid.
+///
+/// Interceptors don't run for autocomplete requests. These are sent as the user types, so would,
+/// for example, use up a user's rate limit before they had executed a single command.
+#[async_trait]
+pub trait CommandInterceptor<R: Runtime>: Send + Sync {
+    async fn before(
+        &self,
+        _oc_client: &Client<R, BotCommandContext>,
+    ) -> Result<(), CommandResponse> {
+        Ok(())
+    }
+
+    async fn after(
+        &self,
+        _oc_client: &Client<R, BotCommandContext>,
+        result: Result<SuccessResult, String>,
+    ) -> Result<SuccessResult, String> {
+        result
+    }
+}
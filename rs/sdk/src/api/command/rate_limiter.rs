@@ -0,0 +1,254 @@
+use crate::api::command::{CommandInterceptor, CommandResponse};
+use crate::oc_api::client::Client;
+use crate::oc_api::Runtime;
+use crate::types::{
+    ActionContext, ActionScope, BotCommandContext, Milliseconds, TimestampMillis, UserId,
+};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+// How often buckets which have refilled completely are removed, so that the memory used is
+// bounded by the number of users or scopes active within this interval
+const PRUNE_INTERVAL: Milliseconds = 60_000;
+
+/// A [`CommandInterceptor`] which limits how often commands can be executed using token buckets.
+///
+/// Buckets can be keyed by the initiating user, by the scope the command was executed in and by
+/// the command name. Each bucket allows `capacity` commands in a burst and is refilled evenly over
+/// `period`. If any bucket for a command is empty the command is rejected with
+/// [`CommandResponse::TooManyRequests`].
+///
+/// ```ignore
+/// let commands = CommandHandlerRegistry::new(oc_client_factory)
+///     .register(Prompt)
+///     .with_interceptor(
+///         RateLimiter::new()
+///             .per_user(5, 60_000)
+///             .per_scope(20, 60_000),
+///     );
+/// ```
+#[derive(Default)]
+pub struct RateLimiter {
+    per_user: Option<RateLimit>,
+    per_scope: Option<RateLimit>,
+    per_command: Option<RateLimit>,
+    commands: Option<HashSet<String>>,
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Default)]
+struct Buckets {
+    buckets: HashMap<BucketKey, TokenBucket>,
+    last_pruned: TimestampMillis,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    pub capacity: u32,
+    pub period: Milliseconds,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum BucketKey {
+    User(UserId),
+    Scope(ActionScope),
+    Command(String),
+}
+
+struct TokenBucket {
+    tokens: u32,
+    updated: TimestampMillis,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn per_user(mut self, capacity: u32, period: Milliseconds) -> Self {
+        self.per_user = Some(RateLimit { capacity, period });
+        self
+    }
+
+    pub fn per_scope(mut self, capacity: u32, period: Milliseconds) -> Self {
+        self.per_scope = Some(RateLimit { capacity, period });
+        self
+    }
+
+    pub fn per_command(mut self, capacity: u32, period: Milliseconds) -> Self {
+        self.per_command = Some(RateLimit { capacity, period });
+        self
+    }
+
+    // By default all commands are rate limited
+    pub fn for_commands(mut self, names: &[&str]) -> Self {
+        self.commands = Some(names.iter().map(|name| name.to_string()).collect());
+        self
+    }
+
+    pub fn try_acquire(
+        &self,
+        initiator: UserId,
+        scope: ActionScope,
+        command: &str,
+        now: TimestampMillis,
+    ) -> bool {
+        if self
+            .commands
+            .as_ref()
+            .is_some_and(|commands| !commands.contains(command))
+        {
+            return true;
+        }
+
+        let keys = [
+            self.per_user.map(|l| (BucketKey::User(initiator), l)),
+            self.per_scope.map(|l| (BucketKey::Scope(scope), l)),
+            self.per_command
+                .map(|l| (BucketKey::Command(command.to_string()), l)),
+        ];
+
+        let mut state = self.buckets.lock().unwrap();
+
+        if now.saturating_sub(state.last_pruned) >= PRUNE_INTERVAL {
+            state.buckets.retain(|key, bucket| {
+                self.limit(key).is_some_and(|limit| {
+                    bucket.refill(limit, now);
+                    bucket.tokens < limit.capacity
+                })
+            });
+            state.last_pruned = now;
+        }
+
+        let buckets = &mut state.buckets;
+
+        // Check every bucket before taking any tokens so that a rejected command doesn't use up
+        // the allowance of the other buckets
+        for (key, limit) in keys.iter().flatten() {
+            if let Some(bucket) = buckets.get_mut(key) {
+                bucket.refill(*limit, now);
+                if bucket.tokens == 0 {
+                    return false;
+                }
+            }
+        }
+
+        for (key, limit) in keys.into_iter().flatten() {
+            let bucket = buckets.entry(key).or_insert(TokenBucket {
+                tokens: limit.capacity,
+                updated: now,
+            });
+            bucket.tokens = bucket.tokens.saturating_sub(1);
+        }
+
+        true
+    }
+
+    fn limit(&self, key: &BucketKey) -> Option<RateLimit> {
+        match key {
+            BucketKey::User(_) => self.per_user,
+            BucketKey::Scope(_) => self.per_scope,
+            BucketKey::Command(_) => self.per_command,
+        }
+    }
+}
+
+impl TokenBucket {
+    fn refill(&mut self, limit: RateLimit, now: TimestampMillis) {
+        let interval = (limit.period / limit.capacity.max(1) as u64).max(1);
+        let added = now.saturating_sub(self.updated) / interval;
+
+        if added == 0 {
+            return;
+        }
+
+        let tokens = (self.tokens as u64).saturating_add(added);
+        if tokens >= limit.capacity as u64 {
+            self.tokens = limit.capacity;
+            self.updated = now;
+        } else {
+            self.tokens = tokens as u32;
+            self.updated += added * interval;
+        }
+    }
+}
+
+#[async_trait]
+impl<R: Runtime> CommandInterceptor<R> for RateLimiter {
+    async fn before(
+        &self,
+        oc_client: &Client<R, BotCommandContext>,
+    ) -> Result<(), CommandResponse> {
+        let context = oc_client.context();
+
+        if self.try_acquire(
+            context.command.initiator,
+            context.scope(),
+            &context.command.name,
+            oc_client.runtime().now(),
+        ) {
+            Ok(())
+        } else {
+            Err(CommandResponse::TooManyRequests)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Chat;
+    use candid::Principal;
+
+    #[test]
+    fn buckets_empty_and_refill() {
+        let limiter = RateLimiter::new().per_user(2, 1000);
+        let user = UserId::from(Principal::anonymous());
+        let scope = ActionScope::Chat(Chat::Direct(Principal::anonymous()));
+
+        assert!(limiter.try_acquire(user, scope, "prompt", 0));
+        assert!(limiter.try_acquire(user, scope, "prompt", 0));
+        assert!(!limiter.try_acquire(user, scope, "prompt", 0));
+        assert!(!limiter.try_acquire(user, scope, "prompt", 499));
+        assert!(limiter.try_acquire(user, scope, "prompt", 500));
+        assert!(!limiter.try_acquire(user, scope, "prompt", 500));
+    }
+
+    #[test]
+    fn rejected_command_does_not_consume_other_buckets() {
+        let limiter = RateLimiter::new()
+            .per_user(1, 1000)
+            .per_scope(2, 1000)
+            .for_commands(&["fractal"]);
+        let user1 = UserId::from(Principal::anonymous());
+        let user2 = UserId::from(Principal::management_canister());
+        let scope = ActionScope::Chat(Chat::Direct(Principal::anonymous()));
+
+        assert!(limiter.try_acquire(user1, scope, "fractal", 0));
+        assert!(!limiter.try_acquire(user1, scope, "fractal", 0));
+        assert!(limiter.try_acquire(user1, scope, "greet", 0));
+        assert!(limiter.try_acquire(user2, scope, "fractal", 0));
+    }
+
+    #[test]
+    fn refilled_buckets_are_pruned() {
+        let limiter = RateLimiter::new().per_user(2, 1000);
+        let user1 = UserId::from(Principal::anonymous());
+        let user2 = UserId::from(Principal::management_canister());
+        let scope = ActionScope::Chat(Chat::Direct(Principal::anonymous()));
+        let bucket_count = || limiter.buckets.lock().unwrap().buckets.len();
+
+        assert!(limiter.try_acquire(user1, scope, "prompt", PRUNE_INTERVAL));
+        assert!(limiter.try_acquire(user2, scope, "prompt", PRUNE_INTERVAL));
+        assert_eq!(bucket_count(), 2);
+
+        // Not yet due to be pruned
+        assert!(limiter.try_acquire(user2, scope, "prompt", 2 * PRUNE_INTERVAL - 1));
+        assert_eq!(bucket_count(), 2);
+
+        // User1's bucket has refilled so is removed, whereas user2's was just used
+        assert!(limiter.try_acquire(user2, scope, "prompt", 2 * PRUNE_INTERVAL));
+        assert_eq!(bucket_count(), 1);
+    }
+}
@@ -2,7 +2,9 @@ use crate::types::ChatRole;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
-pub use crate::types::{BotPermissions, ChatPermission, CommunityPermission, MessagePermission};
+pub use crate::types::{
+    BotPermissions, ChatEventType, ChatPermission, CommunityPermission, MessagePermission,
+};
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct BotDefinition {
@@ -21,12 +23,18 @@ pub struct BotCommandDefinition {
     pub permissions: BotPermissions,
     pub default_role: Option<ChatRole>,
     pub direct_messages: Option<bool>,
+    // A command with subcommands acts as a group, e.g. `/remind at` and `/remind list`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subcommands: Vec<BotCommandDefinition>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Debug, Clone)]
 pub struct AutonomousConfig {
     pub permissions: BotPermissions,
     pub sync_api_key: bool,
+    // The types of chat event OpenChat should notify the bot of via its /notify endpoint
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subscriptions: Vec<ChatEventType>,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -38,6 +46,8 @@ pub struct BotCommandParam {
     pub param_type: BotCommandParamType,
 }
 
+// String and integer params can set `autocomplete`, in which case OpenChat calls the bot's
+// /autocomplete endpoint to get suggestions as the user types
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub enum BotCommandParamType {
     BooleanParam,
@@ -55,6 +65,8 @@ pub struct StringParam {
     pub choices: Vec<BotCommandOptionChoice<String>>,
     #[serde(default)]
     pub multi_line: bool,
+    #[serde(default)]
+    pub autocomplete: bool,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -62,6 +74,8 @@ pub struct IntegerParam {
     pub min_value: i64,
     pub max_value: i64,
     pub choices: Vec<BotCommandOptionChoice<i64>>,
+    #[serde(default)]
+    pub autocomplete: bool,
 }
 
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
@@ -0,0 +1,14 @@
+use crate::api::command::{BadRequest, InternalError};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub use event_handler::{BotEventHandler, BotEventHandlerRegistry};
+
+mod event_handler;
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub enum NotifyResponse {
+    Success,
+    BadRequest(BadRequest),
+    InternalError(InternalError),
+}
@@ -0,0 +1,375 @@
+use crate::api::command::{BadRequest, InternalError};
+use crate::api::event::NotifyResponse;
+use crate::jwt::JwtVerifier;
+use crate::oc_api::client::{Client, ClientFactory};
+use crate::oc_api::Runtime;
+use crate::types::{
+    ActionContext, ActionScope, BotEventContext, BotPermissions, ChatEvent, ChatEventType,
+    TimestampMillis, TokenError,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct BotEventHandlerRegistry<R> {
+    handlers: Vec<Box<dyn BotEventHandler<R>>>,
+    on_installed: Option<LifecycleCallback>,
+    on_uninstalled: Option<LifecycleCallback>,
+    on_permissions_changed: Option<LifecycleCallback>,
+    oc_client_factory: Arc<ClientFactory<R>>,
+}
+
+type LifecycleCallback = Box<dyn Fn(ActionScope, BotPermissions) + Send + Sync + 'static>;
+
+impl<R: Runtime> BotEventHandlerRegistry<R> {
+    pub fn new(oc_client_factory: Arc<ClientFactory<R>>) -> BotEventHandlerRegistry<R> {
+        Self {
+            handlers: Vec::new(),
+            on_installed: None,
+            on_uninstalled: None,
+            on_permissions_changed: None,
+            oc_client_factory,
+        }
+    }
+
+    pub fn register<H: BotEventHandler<R> + 'static>(mut self, handler: H) -> Self {
+        self.handlers.push(Box::new(handler));
+        self
+    }
+
+    // Called when the bot is installed into a chat or community
+    pub fn on_installed(mut self, callback: LifecycleCallback) -> Self {
+        self.on_installed = Some(callback);
+        self
+    }
+
+    // Called when the bot is uninstalled from a chat or community. This is the place to clean up
+    // any api keys or scheduled work for the scope.
+    pub fn on_uninstalled(mut self, callback: LifecycleCallback) -> Self {
+        self.on_uninstalled = Some(callback);
+        self
+    }
+
+    // Called with the newly granted permissions when they are changed for an existing installation
+    pub fn on_permissions_changed(mut self, callback: LifecycleCallback) -> Self {
+        self.on_permissions_changed = Some(callback);
+        self
+    }
+
+    // The event types to declare in `AutonomousConfig::subscriptions`
+    pub fn subscriptions(&self) -> Vec<ChatEventType> {
+        let mut subscriptions = Vec::new();
+
+        // The lifecycle events are membership updates
+        if self.on_installed.is_some()
+            || self.on_uninstalled.is_some()
+            || self.on_permissions_changed.is_some()
+        {
+            subscriptions.push(ChatEventType::MembershipUpdate);
+        }
+
+        for event_type in self.handlers.iter().flat_map(|h| h.subscriptions()) {
+            if !subscriptions.contains(event_type) {
+                subscriptions.push(*event_type);
+            }
+        }
+        subscriptions
+    }
+
+    pub async fn notify(
+        &self,
+        jwt: &str,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+    ) -> NotifyResponse {
+        let context = match BotEventContext::parse(jwt.to_string(), public_key, now) {
+            Ok(context) => context,
+            Err(TokenError::Invalid(error)) => {
+                return NotifyResponse::BadRequest(BadRequest::AccessTokenInvalid(
+                    error.to_string(),
+                ))
+            }
+            Err(TokenError::Expired) => {
+                return NotifyResponse::BadRequest(BadRequest::AccessTokenExpired)
+            }
+        };
+
+        self.handle_lifecycle_event(&context);
+
+        let Some(event_type) = context.event.event.event_type() else {
+            return NotifyResponse::Success;
+        };
+
+        // Every subscribed handler is called, even if an earlier one fails
+        let mut errors = Vec::new();
+        for handler in self
+            .handlers
+            .iter()
+            .filter(|h| h.subscriptions().contains(&event_type))
+        {
+            if let Err(error) = handler
+                .handle(self.oc_client_factory.build(context.clone()))
+                .await
+            {
+                errors.push(error);
+            }
+        }
+
+        if errors.is_empty() {
+            NotifyResponse::Success
+        } else {
+            NotifyResponse::InternalError(InternalError::CommandError(errors.join(", ")))
+        }
+    }
+
+    fn handle_lifecycle_event(&self, context: &BotEventContext) {
+        let callback = match &context.event.event {
+            ChatEvent::BotAdded(e) if e.user_id == context.bot_id => &self.on_installed,
+            ChatEvent::BotRemoved(e) if e.user_id == context.bot_id => &self.on_uninstalled,
+            ChatEvent::BotUpdated(e) if e.user_id == context.bot_id => &self.on_permissions_changed,
+            _ => return,
+        };
+
+        if let Some(callback) = callback {
+            callback(context.scope(), context.granted_permissions.clone());
+        }
+    }
+}
+
+#[async_trait]
+pub trait BotEventHandler<R>: Send + Sync {
+    fn subscriptions(&self) -> &[ChatEventType];
+
+    async fn handle(&self, oc_client: Client<R, BotEventContext>) -> Result<(), String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{MockRuntime, TestTokenIssuer};
+    use crate::types::{
+        BotAdded, BotRemoved, BotUpdated, Chat, EventWrapper, MemberJoined, UserId,
+    };
+    use candid::Principal;
+    use std::sync::Mutex;
+
+    const NOW: TimestampMillis = 1_000_000;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct Recorder {
+        name: &'static str,
+        subscriptions: Vec<ChatEventType>,
+        fail: bool,
+        log: Log,
+    }
+
+    #[async_trait]
+    impl BotEventHandler<MockRuntime> for Recorder {
+        fn subscriptions(&self) -> &[ChatEventType] {
+            &self.subscriptions
+        }
+
+        async fn handle(
+            &self,
+            oc_client: Client<MockRuntime, BotEventContext>,
+        ) -> Result<(), String> {
+            let context = oc_client.context();
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", self.name, context.event.index));
+
+            if self.fail {
+                Err(format!("{} failed", self.name))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn recorder(name: &'static str, subscriptions: &[ChatEventType], log: &Log) -> Recorder {
+        Recorder {
+            name,
+            subscriptions: subscriptions.to_vec(),
+            fail: false,
+            log: log.clone(),
+        }
+    }
+
+    fn registry() -> BotEventHandlerRegistry<MockRuntime> {
+        BotEventHandlerRegistry::new(Arc::new(ClientFactory::new(MockRuntime::new())))
+    }
+
+    fn scope() -> ActionScope {
+        ActionScope::Chat(Chat::Group(Principal::anonymous()))
+    }
+
+    fn event_jwt(issuer: &TestTokenIssuer, event: ChatEvent, expiry: TimestampMillis) -> String {
+        issuer.event_jwt(
+            scope(),
+            EventWrapper {
+                index: 7,
+                timestamp: NOW,
+                correlation_id: 0,
+                expires_at: None,
+                event,
+            },
+            BotPermissions::text_only(),
+            expiry,
+        )
+    }
+
+    fn member_joined() -> ChatEvent {
+        ChatEvent::ParticipantJoined(MemberJoined {
+            user_id: UserId::from(Principal::management_canister()),
+            invited_by: None,
+        })
+    }
+
+    fn notify(registry: &BotEventHandlerRegistry<MockRuntime>, event: ChatEvent) -> NotifyResponse {
+        let issuer = TestTokenIssuer::new();
+        let jwt = event_jwt(&issuer, event, NOW + 60_000);
+
+        MockRuntime::new().block_on(registry.notify(&jwt, issuer.public_key(), NOW))
+    }
+
+    fn take(log: &Log) -> Vec<String> {
+        std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    #[test]
+    fn only_handlers_subscribed_to_the_event_type_are_called() {
+        let log = Log::default();
+        let registry = registry()
+            .register(recorder(
+                "members",
+                &[ChatEventType::MembershipUpdate],
+                &log,
+            ))
+            .register(recorder("messages", &[ChatEventType::Message], &log))
+            .register(recorder(
+                "both",
+                &[ChatEventType::Message, ChatEventType::MembershipUpdate],
+                &log,
+            ));
+
+        assert!(matches!(
+            notify(&registry, member_joined()),
+            NotifyResponse::Success
+        ));
+        assert_eq!(take(&log), ["members 7", "both 7"]);
+
+        // Events without a type aren't passed to any handler
+        assert!(matches!(
+            notify(&registry, ChatEvent::Empty),
+            NotifyResponse::Success
+        ));
+        assert!(take(&log).is_empty());
+    }
+
+    #[test]
+    fn failing_handler_does_not_stop_the_others() {
+        let log = Log::default();
+        let registry = registry()
+            .register(Recorder {
+                fail: true,
+                ..recorder("first", &[ChatEventType::MembershipUpdate], &log)
+            })
+            .register(recorder("second", &[ChatEventType::MembershipUpdate], &log));
+
+        let NotifyResponse::InternalError(InternalError::CommandError(error)) =
+            notify(&registry, member_joined())
+        else {
+            panic!("Expected the handler's error to be returned");
+        };
+        assert_eq!(error, "first failed");
+        assert_eq!(take(&log), ["first 7", "second 7"]);
+    }
+
+    #[test]
+    fn invalid_tokens_are_rejected() {
+        let log = Log::default();
+        let registry = registry().register(recorder(
+            "members",
+            &[ChatEventType::MembershipUpdate],
+            &log,
+        ));
+        let issuer = TestTokenIssuer::new();
+        let notify =
+            |jwt: &str| MockRuntime::new().block_on(registry.notify(jwt, issuer.public_key(), NOW));
+
+        let expired = event_jwt(&issuer, member_joined(), NOW - 60_000);
+        assert!(matches!(
+            notify(&expired),
+            NotifyResponse::BadRequest(BadRequest::AccessTokenExpired)
+        ));
+
+        let foreign = event_jwt(&TestTokenIssuer::new(), member_joined(), NOW + 60_000);
+        assert!(matches!(
+            notify(&foreign),
+            NotifyResponse::BadRequest(BadRequest::AccessTokenInvalid(_))
+        ));
+
+        // A token for a different action isn't accepted as an event
+        let api_key = issuer.api_key_jwt(scope(), BotPermissions::text_only(), NOW + 60_000);
+        assert!(matches!(
+            notify(&api_key),
+            NotifyResponse::BadRequest(BadRequest::AccessTokenInvalid(_))
+        ));
+
+        assert!(take(&log).is_empty());
+    }
+
+    fn bot_event(user_id: UserId) -> [ChatEvent; 3] {
+        let by = UserId::from(Principal::management_canister());
+        [
+            ChatEvent::BotAdded(BotAdded {
+                user_id,
+                added_by: by,
+            }),
+            ChatEvent::BotUpdated(BotUpdated {
+                user_id,
+                updated_by: by,
+            }),
+            ChatEvent::BotRemoved(BotRemoved {
+                user_id,
+                removed_by: by,
+            }),
+        ]
+    }
+
+    #[test]
+    fn lifecycle_events_for_this_bot_are_routed_to_callbacks() {
+        let log = Log::default();
+        let callback = |name: &'static str| -> LifecycleCallback {
+            let log = log.clone();
+            Box::new(move |scope, _| log.lock().unwrap().push(format!("{name} {scope:?}")))
+        };
+        let registry = registry()
+            .on_installed(callback("installed"))
+            .on_permissions_changed(callback("updated"))
+            .on_uninstalled(callback("uninstalled"));
+
+        assert_eq!(registry.subscriptions(), [ChatEventType::MembershipUpdate]);
+
+        // `TestTokenIssuer` mints tokens for the anonymous principal by default
+        for event in bot_event(Principal::anonymous().into()) {
+            assert!(matches!(notify(&registry, event), NotifyResponse::Success));
+        }
+        let scope = scope();
+        assert_eq!(
+            take(&log),
+            [
+                format!("installed {scope:?}"),
+                format!("updated {scope:?}"),
+                format!("uninstalled {scope:?}")
+            ]
+        );
+
+        // Another bot being added to or removed from the chat is ignored
+        for event in bot_event(Principal::from_slice(&[1]).into()) {
+            assert!(matches!(notify(&registry, event), NotifyResponse::Success));
+        }
+        assert!(take(&log).is_empty());
+    }
+}
@@ -1,2 +1,3 @@
 pub mod command;
 pub mod definition;
+pub mod event;
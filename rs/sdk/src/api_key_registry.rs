@@ -77,7 +77,64 @@ impl ApiKeyRegistry {
         self.api_keys.remove(scope);
     }
 
+    // Removes the api key for the given scope and, if it is a community, those of its channels
+    pub fn remove_all_within(&mut self, scope: &ActionScope) {
+        match scope {
+            ActionScope::Community(community_id) => self.api_keys.retain(|s, _| match s {
+                ActionScope::Community(id) => id != community_id,
+                ActionScope::Chat(Chat::Channel(id, _)) => id != community_id,
+                _ => true,
+            }),
+            ActionScope::Chat(_) => self.remove(scope),
+        }
+    }
+
     pub fn count(&self) -> usize {
         self.api_keys.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use candid::Principal;
+
+    fn registry(scopes: &[ActionScope]) -> ApiKeyRegistry {
+        let mut registry = ApiKeyRegistry::default();
+        for scope in scopes {
+            registry.api_keys.insert(
+                *scope,
+                ApiKeyRecord {
+                    token: String::new(),
+                    granted_permissions: BotPermissions::text_only(),
+                },
+            );
+        }
+        registry
+    }
+
+    #[test]
+    fn remove_all_within_community_removes_its_channels() {
+        let community = Principal::from_slice(&[1]);
+        let other_community = Principal::from_slice(&[2]);
+        let group = ActionScope::Chat(Chat::Group(Principal::from_slice(&[3])));
+        let other_channel = ActionScope::Chat(Chat::Channel(other_community, 1));
+
+        let mut registry = registry(&[
+            ActionScope::Community(community),
+            ActionScope::Chat(Chat::Channel(community, 1)),
+            ActionScope::Chat(Chat::Channel(community, 2)),
+            ActionScope::Community(other_community),
+            other_channel,
+            group,
+        ]);
+
+        registry.remove_all_within(&ActionScope::Community(community));
+        assert_eq!(registry.count(), 3);
+        assert!(registry.get(&other_channel).is_some());
+
+        registry.remove_all_within(&group);
+        assert_eq!(registry.count(), 2);
+        assert!(registry.get(&group).is_none());
+    }
+}
@@ -1,9 +1,16 @@
+// Allows the `CommandArgs` derive macro to refer to `::oc_bots_sdk` from within this crate
+extern crate self as oc_bots_sdk;
+
 pub mod api;
 mod api_key_registry;
 pub mod mainnet;
 pub mod oc_api;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod trusted_keys;
 pub mod types;
 mod utils;
 
 pub use api_key_registry::ApiKeyRegistry;
+pub use trusted_keys::{TrustedKey, TrustedKeys};
 pub use utils::*;
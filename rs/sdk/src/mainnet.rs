@@ -1,3 +1,5 @@
+use crate::TrustedKeys;
+
 pub const IC_URL: &str = "https://icp0.io";
 pub const OC_PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----\nMFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEquEHzJr9605Oy796e4z7LKow46DV\nNUnDOQWavi86vEhRAAfdbVh/Lgmxfi44LPb6S0wnCRm9kI/XdK1DYw2Eaw==\n-----END PUBLIC KEY-----\n";
 
@@ -8,3 +10,7 @@ pub fn mainnet_ic_url() -> String {
 pub fn mainnet_oc_public_key() -> String {
     OC_PUBLIC_KEY.to_string()
 }
+
+pub fn mainnet_oc_trusted_keys() -> TrustedKeys {
+    TrustedKeys::from_pem(OC_PUBLIC_KEY).expect("Invalid OpenChat public key")
+}
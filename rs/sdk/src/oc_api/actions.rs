@@ -1,23 +1,43 @@
-use super::Runtime;
-use crate::types::{CallResult, CanisterId};
+use super::{RetryPolicy, Runtime};
+use crate::types::{BotPermissions, CallResult, CanisterId, OcApiError};
 use candid::CandidType;
 use serde::de::DeserializeOwned;
 use std::future::Future;
 use std::sync::Arc;
 
+pub mod add_members;
+pub mod add_reaction;
+pub mod change_role;
 pub mod chat_details;
 pub mod chat_events;
+pub mod chat_members;
 pub mod create_channel;
 pub mod delete_channel;
+pub mod delete_messages;
+pub mod edit_message;
+pub mod invite_users;
+pub mod pin_message;
+pub mod remove_reaction;
+pub mod remove_user;
 pub mod send_message;
+pub mod unpin_message;
+pub mod update_chat;
 
 pub trait ActionDef {
     type Args: CandidType + Clone + Send + 'static;
-    type Response: CandidType + DeserializeOwned;
+    type Response: CandidType + DeserializeOwned + ActionResponse;
 
     fn method_name(is_canister_runtime: bool) -> &'static str;
 }
 
+// Converts an action's response into a `Result` so that failures can be handled with `?`, eg.
+// `client.chat_details().execute_async().await?.into_result()?`
+pub trait ActionResponse {
+    type Success;
+
+    fn into_result(self) -> Result<Self::Success, OcApiError>;
+}
+
 pub trait ActionArgsBuilder<R: Runtime>: Sized {
     type Action: ActionDef;
 
@@ -27,6 +47,47 @@ pub trait ActionArgsBuilder<R: Runtime>: Sized {
 
     fn into_args(self) -> <Self::Action as ActionDef>::Args;
 
+    // The permissions the bot must have been granted for the action to succeed. If these are
+    // known not to have been granted then the action fails without calling OpenChat.
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        None
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        None
+    }
+
+    fn check_permissions(&self) -> CallResult<()> {
+        if let (Some(required), Some(granted)) =
+            (self.required_permissions(), self.granted_permissions())
+        {
+            if !required.is_subset(granted) {
+                return Err(OcApiError::MissingPermissions(required));
+            }
+        }
+
+        Ok(())
+    }
+
+    // By default the retry policy set on the `ClientFactory` (if any) is used
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        None
+    }
+
+    // False if making the call twice could carry out the action twice, eg. sending a message
+    // without a `message_id`. Timeouts aren't retried for such actions since the first call may
+    // have succeeded.
+    fn is_idempotent(&self) -> bool {
+        true
+    }
+
+    fn with_retry(self, retry_policy: RetryPolicy) -> WithRetry<Self> {
+        WithRetry {
+            builder: self,
+            retry_policy,
+        }
+    }
+
     fn execute<
         F: FnOnce(
                 <Self::Action as ActionDef>::Args,
@@ -43,13 +104,26 @@ pub trait ActionArgsBuilder<R: Runtime>: Sized {
         let runtime_clone = runtime.clone();
         let api_gateway = self.api_gateway();
         let method_name = Self::Action::method_name(is_canister_runtime);
+        let permissions_check = self.check_permissions();
+        let retry_policy = self.retry_policy();
+        let is_idempotent = self.is_idempotent();
         let args = self.into_args();
 
+        if let Err(error) = permissions_check {
+            on_response(args, Err(error));
+            return;
+        }
+
         runtime.spawn(async move {
-            let response = runtime_clone
-                .call_canister(api_gateway, method_name, (args.clone(),))
-                .await
-                .map(|(r,)| r);
+            let response = call_with_retry::<R, Self::Action>(
+                runtime_clone,
+                api_gateway,
+                method_name,
+                args.clone(),
+                retry_policy,
+                is_idempotent,
+            )
+            .await;
 
             on_response(args, response);
         });
@@ -62,13 +136,224 @@ pub trait ActionArgsBuilder<R: Runtime>: Sized {
         let api_gateway = self.api_gateway();
         let is_canister_runtime = runtime.is_canister();
         let method_name = Self::Action::method_name(is_canister_runtime);
+        let permissions_check = self.check_permissions();
+        let retry_policy = self.retry_policy();
+        let is_idempotent = self.is_idempotent();
         let args = self.into_args();
 
         async move {
-            runtime
-                .call_canister(api_gateway, method_name, (args,))
-                .await
-                .map(|(r,)| r)
+            permissions_check?;
+
+            call_with_retry::<R, Self::Action>(
+                runtime,
+                api_gateway,
+                method_name,
+                args,
+                retry_policy,
+                is_idempotent,
+            )
+            .await
+        }
+    }
+}
+
+// Wraps an `ActionArgsBuilder` to override its retry policy
+pub struct WithRetry<B> {
+    builder: B,
+    retry_policy: RetryPolicy,
+}
+
+impl<R: Runtime, B: ActionArgsBuilder<R>> ActionArgsBuilder<R> for WithRetry<B> {
+    type Action = B::Action;
+
+    fn runtime(&self) -> Arc<R> {
+        self.builder.runtime()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.builder.api_gateway()
+    }
+
+    fn into_args(self) -> <Self::Action as ActionDef>::Args {
+        self.builder.into_args()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        self.builder.required_permissions()
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.builder.granted_permissions()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        Some(self.retry_policy.clone())
+    }
+
+    fn is_idempotent(&self) -> bool {
+        self.builder.is_idempotent()
+    }
+}
+
+async fn call_with_retry<R: Runtime, A: ActionDef>(
+    runtime: Arc<R>,
+    api_gateway: CanisterId,
+    method_name: &'static str,
+    args: A::Args,
+    retry_policy: Option<RetryPolicy>,
+    is_idempotent: bool,
+) -> CallResult<A::Response> {
+    let max_attempts = retry_policy.as_ref().map_or(1, |p| p.max_attempts().max(1));
+    let mut attempt = 1;
+
+    loop {
+        let error = match runtime
+            .call_canister(api_gateway, method_name, (args.clone(),))
+            .await
+        {
+            Ok((response,)) => return Ok(response),
+            Err(error) => error,
+        };
+
+        if attempt >= max_attempts
+            || !error.is_retryable()
+            || (!is_idempotent && error == OcApiError::Timeout)
+        {
+            return Err(error);
         }
+
+        if let Some(retry_policy) = retry_policy.as_ref() {
+            runtime.sleep(retry_policy.backoff(attempt)).await;
+        }
+
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oc_api::client::Client;
+    use crate::testing::MockRuntime;
+    use crate::types::{ActionScope, AuthToken, BotApiKeyContext, MessageId};
+    use candid::Principal;
+    use delete_channel::DeleteChannelAction;
+    use send_message::SendMessageAction;
+
+    fn context() -> BotApiKeyContext {
+        BotApiKeyContext {
+            token: AuthToken::ApiKey(String::new()),
+            bot_id: Principal::anonymous().into(),
+            api_gateway: Principal::anonymous(),
+            scope: ActionScope::Community(Principal::anonymous()),
+            granted_permissions: BotPermissions::default(),
+        }
+    }
+
+    #[test]
+    fn failure_responses_map_to_typed_errors() {
+        assert_eq!(
+            add_reaction::Response::MessageNotFound.into_result(),
+            Err(OcApiError::MessageNotFound)
+        );
+        assert_eq!(
+            send_message::Response::ThreadNotFound.into_result().err(),
+            Some(OcApiError::ThreadNotFound)
+        );
+        assert_eq!(
+            edit_message::Response::MessageAlreadyFinalised.into_result(),
+            Err(OcApiError::MessageAlreadyFinalised)
+        );
+        assert_eq!(
+            remove_user::Response::UserNotInChat.into_result(),
+            Err(OcApiError::UserNotInChat)
+        );
+        assert_eq!(
+            update_chat::Response::NameTaken.into_result(),
+            Err(OcApiError::NameTaken)
+        );
+        assert_eq!(
+            add_members::Response::UserLimitReached(100).into_result(),
+            Err(OcApiError::UserLimitReached(100))
+        );
+        assert_eq!(
+            pin_message::Response::NotAuthorized.into_result(),
+            Err(OcApiError::NotAuthorized)
+        );
+    }
+
+    #[test]
+    fn no_change_is_success() {
+        assert_eq!(add_reaction::Response::NoChange.into_result(), Ok(()));
+        assert_eq!(remove_reaction::Response::NoChange.into_result(), Ok(()));
+        assert_eq!(pin_message::Response::NoChange.into_result(), Ok(()));
+        assert_eq!(unpin_message::Response::NoChange.into_result(), Ok(()));
+    }
+
+    #[test]
+    fn retries_are_recorded_and_advance_the_clock() {
+        let runtime = Arc::new(MockRuntime::new());
+        let mut attempts = 0;
+        runtime.on::<DeleteChannelAction, _>(move |_| {
+            attempts += 1;
+            if attempts < 3 {
+                Err(OcApiError::Timeout)
+            } else {
+                Ok(delete_channel::Response::Success)
+            }
+        });
+
+        let client = Client::new(runtime.clone(), context());
+
+        let response = runtime.block_on(
+            client
+                .delete_channel(1)
+                .with_retry(RetryPolicy::new(3))
+                .execute_async(),
+        );
+
+        assert!(matches!(response, Ok(delete_channel::Response::Success)));
+        assert_eq!(runtime.calls_to::<DeleteChannelAction>().len(), 3);
+        assert_eq!(runtime.now(), 3_000);
+    }
+
+    #[test]
+    fn timeouts_are_only_retried_if_the_message_has_an_id() {
+        let runtime = Arc::new(MockRuntime::new());
+        runtime.fail_with::<SendMessageAction>(OcApiError::Timeout);
+        let client = Client::new(runtime.clone(), context());
+
+        let send = |message_id: Option<MessageId>| {
+            let mut builder = client.send_text_message("Hello".to_string());
+            if let Some(message_id) = message_id {
+                builder = builder.with_message_id(message_id);
+            }
+            runtime.block_on(builder.with_retry(RetryPolicy::new(3)).execute_async())
+        };
+
+        assert_eq!(send(None).err(), Some(OcApiError::Timeout));
+        assert_eq!(runtime.calls_to::<SendMessageAction>().len(), 1);
+
+        runtime.clear_calls();
+        assert_eq!(send(Some(1.into())).err(), Some(OcApiError::Timeout));
+        assert_eq!(runtime.calls_to::<SendMessageAction>().len(), 3);
+    }
+
+    #[test]
+    fn canisters_back_off_between_attempts() {
+        let runtime = Arc::new(MockRuntime::new().with_is_canister(true));
+        runtime.fail_with::<DeleteChannelAction>(OcApiError::Timeout);
+        let client = Client::new(runtime.clone(), context());
+
+        let response = runtime.block_on(
+            client
+                .delete_channel(1)
+                .with_retry(RetryPolicy::new(3))
+                .execute_async(),
+        );
+
+        assert_eq!(response.err(), Some(OcApiError::Timeout));
+        assert_eq!(runtime.calls_to::<DeleteChannelAction>().len(), 3);
+        assert_eq!(runtime.now(), 3_000);
     }
 }
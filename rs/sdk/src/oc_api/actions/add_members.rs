@@ -0,0 +1,53 @@
+use crate::oc_api::actions::{ActionDef, ActionResponse};
+use crate::types::{AuthToken, ChannelId, OCError, OcApiError, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub struct AddMembersAction;
+
+impl ActionDef for AddMembersAction {
+    type Args = Args;
+    type Response = Response;
+
+    fn method_name(_: bool) -> &'static str {
+        "bot_add_members"
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub user_ids: Vec<UserId>,
+    pub auth_token: AuthToken,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FailedAuthentication(String),
+    InvalidRequest(String),
+    NotAuthorized,
+    Frozen,
+    UserLimitReached(u32),
+    C2CError(i32, String),
+    Error(OCError),
+}
+
+impl ActionResponse for Response {
+    type Success = ();
+
+    fn into_result(self) -> Result<(), OcApiError> {
+        match self {
+            Response::Success => Ok(()),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::UserLimitReached(value) => Err(OcApiError::UserLimitReached(value)),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
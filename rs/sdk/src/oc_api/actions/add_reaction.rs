@@ -0,0 +1,58 @@
+use crate::oc_api::actions::{ActionDef, ActionResponse};
+use crate::types::{AuthToken, ChannelId, MessageId, MessageIndex, OCError, OcApiError};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub struct AddReactionAction;
+
+impl ActionDef for AddReactionAction {
+    type Args = Args;
+    type Response = Response;
+
+    fn method_name(_: bool) -> &'static str {
+        "bot_add_reaction"
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub reaction: String,
+    pub auth_token: AuthToken,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FailedAuthentication(String),
+    InvalidRequest(String),
+    NotAuthorized,
+    Frozen,
+    MessageNotFound,
+    NoChange,
+    C2CError(i32, String),
+    Error(OCError),
+}
+
+impl ActionResponse for Response {
+    type Success = ();
+
+    fn into_result(self) -> Result<(), OcApiError> {
+        match self {
+            Response::Success => Ok(()),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::MessageNotFound => Err(OcApiError::MessageNotFound),
+            // The reaction was already there, which is what the caller wanted
+            Response::NoChange => Ok(()),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::oc_api::actions::{ActionDef, ActionResponse};
+use crate::types::{AuthToken, ChannelId, ChatRole, OCError, OcApiError, UserId};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub struct ChangeRoleAction;
+
+impl ActionDef for ChangeRoleAction {
+    type Args = Args;
+    type Response = Response;
+
+    fn method_name(_: bool) -> &'static str {
+        "bot_change_role"
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub user_id: UserId,
+    pub new_role: ChatRole,
+    pub auth_token: AuthToken,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FailedAuthentication(String),
+    InvalidRequest(String),
+    NotAuthorized,
+    Frozen,
+    UserNotInChat,
+    CannotChangeRole,
+    C2CError(i32, String),
+    Error(OCError),
+}
+
+impl ActionResponse for Response {
+    type Success = ();
+
+    fn into_result(self) -> Result<(), OcApiError> {
+        match self {
+            Response::Success => Ok(()),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::UserNotInChat => Err(OcApiError::UserNotInChat),
+            Response::CannotChangeRole => Err(OcApiError::CannotChangeRole),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
@@ -3,10 +3,10 @@ use serde::Serialize;
 
 use crate::types::{
     AccessGateConfig, AuthToken, ChannelId, ChatPermissions, EventIndex, FrozenGroupInfo,
-    MessageIndex, Milliseconds, OCError, TimestampMillis, VersionedRules, VideoCall,
+    MessageIndex, Milliseconds, OCError, OcApiError, TimestampMillis, VersionedRules, VideoCall,
 };
 
-use super::ActionDef;
+use super::{ActionDef, ActionResponse};
 
 pub struct ChatDetailsAction;
 
@@ -44,6 +44,24 @@ pub enum Response {
     Error(OCError),
 }
 
+impl ActionResponse for Response {
+    type Success = ChatDetails;
+
+    fn into_result(self) -> Result<ChatDetails, OcApiError> {
+        match self {
+            Response::Success(result) => Ok(result),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::DirectChatUnsupported => Err(OcApiError::DirectChatUnsupported),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::NotFound => Err(OcApiError::NotFound),
+            Response::InternalError(message) => Err(OcApiError::InternalError(message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct ChatDetails {
     pub name: String,
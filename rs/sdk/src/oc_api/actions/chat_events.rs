@@ -2,11 +2,11 @@ use candid::{CandidType, Deserialize};
 use serde::Serialize;
 
 use crate::types::{
-    AuthToken, ChannelId, ChatEvent, EventIndex, EventWrapper, MessageIndex, OCError,
+    AuthToken, ChannelId, ChatEvent, EventIndex, EventWrapper, MessageIndex, OCError, OcApiError,
     TimestampMillis,
 };
 
-use super::ActionDef;
+use super::{ActionDef, ActionResponse};
 
 pub struct ChatEventsAction;
 
@@ -43,6 +43,23 @@ pub enum Response {
     Error(OCError),
 }
 
+impl ActionResponse for Response {
+    type Success = EventsResponse;
+
+    fn into_result(self) -> Result<EventsResponse, OcApiError> {
+        match self {
+            Response::Success(result) => Ok(result),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::NotFound => Err(OcApiError::NotFound),
+            Response::InternalError(message) => Err(OcApiError::InternalError(message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Debug)]
 pub struct EventsResponse {
     pub events: Vec<EventWrapper<ChatEvent>>,
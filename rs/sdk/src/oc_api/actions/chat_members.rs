@@ -0,0 +1,82 @@
+use candid::{CandidType, Deserialize};
+use serde::Serialize;
+
+use crate::types::{AuthToken, ChannelId, ChatRole, OCError, OcApiError, TimestampMillis, UserId};
+
+use super::{ActionDef, ActionResponse};
+
+pub struct ChatMembersAction;
+
+impl ActionDef for ChatMembersAction {
+    type Args = Args;
+    type Response = Response;
+
+    fn method_name(is_canister_runtime: bool) -> &'static str {
+        // `bot_chat_members` is a composite query which means it can't (currently) be called in
+        // replicated mode, so canisters must call `bot_chat_members_c2c` instead which is an
+        // update call.
+        if is_canister_runtime {
+            "bot_chat_members_c2c"
+        } else {
+            "bot_chat_members"
+        }
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub offset: u32,
+    pub max_results: u32,
+    pub auth_token: AuthToken,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success(ChatMembersPage),
+    FailedAuthentication(String),
+    DirectChatUnsupported,
+    NotAuthorized,
+    NotFound,
+    InternalError(String),
+    Error(OCError),
+}
+
+impl ActionResponse for Response {
+    type Success = ChatMembersPage;
+
+    fn into_result(self) -> Result<ChatMembersPage, OcApiError> {
+        match self {
+            Response::Success(result) => Ok(result),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::DirectChatUnsupported => Err(OcApiError::DirectChatUnsupported),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::NotFound => Err(OcApiError::NotFound),
+            Response::InternalError(message) => Err(OcApiError::InternalError(message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatMembersPage {
+    pub members: Vec<ChatMember>,
+    pub total: u32,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct ChatMember {
+    pub user_id: UserId,
+    pub role: ChatRole,
+    pub joined: TimestampMillis,
+}
+
+impl ChatMembersPage {
+    // The offset to request the next page from, or None if this is the last page
+    pub fn next_offset(&self, offset: u32) -> Option<u32> {
+        let next = offset.saturating_add(self.members.len() as u32);
+        (!self.members.is_empty() && next < self.total).then_some(next)
+    }
+}
@@ -1,6 +1,7 @@
-use crate::oc_api::actions::ActionDef;
+use crate::oc_api::actions::{ActionDef, ActionResponse};
 use crate::types::{
-    AccessGateConfig, AuthToken, ChannelId, ChatPermissions, Document, Milliseconds, OCError, Rules,
+    AccessGateConfig, AuthToken, ChannelId, ChatPermissions, Document, Milliseconds, OCError,
+    OcApiError, Rules,
 };
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
@@ -43,6 +44,24 @@ pub enum Response {
     Error(OCError),
 }
 
+impl ActionResponse for Response {
+    type Success = SuccessResult;
+
+    fn into_result(self) -> Result<SuccessResult, OcApiError> {
+        match self {
+            Response::Success(result) => Ok(result),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct SuccessResult {
     pub channel_id: ChannelId,
@@ -1,5 +1,5 @@
-use crate::oc_api::actions::ActionDef;
-use crate::types::{AuthToken, ChannelId, OCError};
+use crate::oc_api::actions::{ActionDef, ActionResponse};
+use crate::types::{AuthToken, ChannelId, OCError, OcApiError};
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 
@@ -31,3 +31,22 @@ pub enum Response {
     C2CError(i32, String),
     Error(OCError),
 }
+
+impl ActionResponse for Response {
+    type Success = ();
+
+    fn into_result(self) -> Result<(), OcApiError> {
+        match self {
+            Response::Success => Ok(()),
+            Response::ChannelNotFound => Err(OcApiError::ChannelNotFound),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
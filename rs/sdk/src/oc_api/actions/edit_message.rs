@@ -0,0 +1,61 @@
+use crate::oc_api::actions::{ActionDef, ActionResponse};
+use crate::types::{
+    AuthToken, ChannelId, MessageContentInitial, MessageId, MessageIndex, OCError, OcApiError,
+};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub struct EditMessageAction;
+
+impl ActionDef for EditMessageAction {
+    type Args = Args;
+    type Response = Response;
+
+    fn method_name(_: bool) -> &'static str {
+        "bot_edit_message"
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub thread: Option<MessageIndex>,
+    pub message_id: MessageId,
+    pub content: MessageContentInitial,
+    pub block_level_markdown: bool,
+    pub finalised: bool,
+    pub auth_token: AuthToken,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FailedAuthentication(String),
+    InvalidRequest(String),
+    NotAuthorized,
+    Frozen,
+    MessageNotFound,
+    MessageAlreadyFinalised,
+    C2CError(i32, String),
+    Error(OCError),
+}
+
+impl ActionResponse for Response {
+    type Success = ();
+
+    fn into_result(self) -> Result<(), OcApiError> {
+        match self {
+            Response::Success => Ok(()),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::MessageNotFound => Err(OcApiError::MessageNotFound),
+            Response::MessageAlreadyFinalised => Err(OcApiError::MessageAlreadyFinalised),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
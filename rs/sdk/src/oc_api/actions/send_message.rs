@@ -1,7 +1,7 @@
-use crate::oc_api::actions::ActionDef;
+use crate::oc_api::actions::{ActionDef, ActionResponse};
 use crate::types::{
     AuthToken, ChannelId, EventIndex, MessageContentInitial, MessageId, MessageIndex, OCError,
-    TimestampMillis,
+    OcApiError, TimestampMillis, UserId,
 };
 use candid::{CandidType, Deserialize};
 use serde::Serialize;
@@ -21,7 +21,11 @@ impl ActionDef for SendMessageAction {
 pub struct Args {
     pub channel_id: Option<ChannelId>,
     pub message_id: Option<MessageId>,
+    pub thread: Option<MessageIndex>,
+    pub replies_to: Option<MessageIndex>,
     pub content: MessageContentInitial,
+    pub mentioned: Vec<UserId>,
+    pub forwarding: bool,
     pub block_level_markdown: bool,
     pub finalised: bool,
     pub auth_token: AuthToken,
@@ -40,6 +44,26 @@ pub enum Response {
     Error(OCError),
 }
 
+impl ActionResponse for Response {
+    type Success = SuccessResult;
+
+    fn into_result(self) -> Result<SuccessResult, OcApiError> {
+        match self {
+            Response::Success(result) => Ok(result),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::ThreadNotFound => Err(OcApiError::ThreadNotFound),
+            Response::MessageAlreadyFinalised => Err(OcApiError::MessageAlreadyFinalised),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub struct SuccessResult {
     pub message_id: MessageId,
@@ -0,0 +1,64 @@
+use crate::oc_api::actions::{ActionDef, ActionResponse};
+use crate::types::{
+    AccessGateConfig, AuthToken, ChannelId, ChatPermissions, Document, Milliseconds, OCError,
+    OcApiError, OptionUpdate, Rules,
+};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+pub struct UpdateChatAction;
+
+impl ActionDef for UpdateChatAction {
+    type Args = Args;
+    type Response = Response;
+
+    fn method_name(_: bool) -> &'static str {
+        "bot_update_chat"
+    }
+}
+
+// Fields which are `None` or `OptionUpdate::NoChange` are left unchanged
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct Args {
+    pub channel_id: Option<ChannelId>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub rules: Option<Rules>,
+    pub avatar: OptionUpdate<Document>,
+    pub permissions: Option<ChatPermissions>,
+    pub events_ttl: OptionUpdate<Milliseconds>,
+    pub gate_config: OptionUpdate<AccessGateConfig>,
+    pub external_url: OptionUpdate<String>,
+    pub auth_token: AuthToken,
+}
+
+#[derive(CandidType, Serialize, Deserialize, Debug)]
+pub enum Response {
+    Success,
+    FailedAuthentication(String),
+    InvalidRequest(String),
+    NotAuthorized,
+    Frozen,
+    NameTaken,
+    C2CError(i32, String),
+    Error(OCError),
+}
+
+impl ActionResponse for Response {
+    type Success = ();
+
+    fn into_result(self) -> Result<(), OcApiError> {
+        match self {
+            Response::Success => Ok(()),
+            Response::FailedAuthentication(message) => {
+                Err(OcApiError::FailedAuthentication(message))
+            }
+            Response::InvalidRequest(message) => Err(OcApiError::InvalidRequest(message)),
+            Response::NotAuthorized => Err(OcApiError::NotAuthorized),
+            Response::Frozen => Err(OcApiError::Frozen),
+            Response::NameTaken => Err(OcApiError::NameTaken),
+            Response::C2CError(code, message) => Err(OcApiError::C2CError(code, message)),
+            Response::Error(error) => Err(OcApiError::OCError(error)),
+        }
+    }
+}
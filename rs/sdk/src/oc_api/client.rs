@@ -1,48 +1,109 @@
 use crate::oc_api::actions::chat_events::EventsSelectionCriteria;
-use crate::oc_api::Runtime;
-use crate::types::{ActionContext, ChannelId, MessageContentInitial, TextContent};
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{
+    ActionContext, ActionScope, BotPermissions, ChannelId, ChatPermission, ChatRole,
+    CommunityPermission, MessageContentInitial, MessageId, MessageIndex, TextContent, UserId,
+};
+use add_members::AddMembersBuilder;
+use add_reaction::AddReactionBuilder;
+use change_role::ChangeRoleBuilder;
 use chat_details::ChatDetailsBuilder;
 use chat_events::ChatEventsBuilder;
+use chat_members::ChatMembersBuilder;
 use create_channel::CreateChannelBuilder;
 use delete_channel::DeleteChannelBuilder;
+use delete_messages::DeleteMessagesBuilder;
+use edit_message::EditMessageBuilder;
+use invite_users::InviteUsersBuilder;
+use pin_message::PinMessageBuilder;
+use remove_reaction::RemoveReactionBuilder;
+use remove_user::RemoveUserBuilder;
 use send_message::SendMessageBuilder;
 use std::sync::Arc;
+use unpin_message::UnpinMessageBuilder;
+use update_chat::UpdateChatBuilder;
 
+mod add_members;
+mod add_reaction;
+mod change_role;
 mod chat_details;
 mod chat_events;
+mod chat_members;
 mod create_channel;
 mod delete_channel;
+mod delete_messages;
+mod edit_message;
+mod invite_users;
+mod pin_message;
+mod remove_reaction;
+mod remove_user;
 mod send_message;
+mod unpin_message;
+mod update_chat;
 
 pub struct ClientFactory<R> {
     runtime: Arc<R>,
+    retry_policy: Option<RetryPolicy>,
 }
 
 impl<R: Runtime> ClientFactory<R> {
     pub fn new(runtime: R) -> Self {
         Self {
             runtime: Arc::new(runtime),
+            retry_policy: None,
         }
     }
 
+    // The retry policy applied to every action executed by the clients built by this factory.
+    // This can be overridden per action by calling `with_retry` on the action's builder.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
     pub fn build<C>(&self, context: C) -> Client<R, C> {
-        Client::new(self.runtime.clone(), context)
+        Client::new(self.runtime.clone(), context).with_retry_policy(self.retry_policy.clone())
     }
 }
 
 pub struct Client<R, C> {
     runtime: Arc<R>,
     context: C,
+    retry_policy: Option<RetryPolicy>,
 }
 
 impl<R, C> Client<R, C> {
     pub fn new(runtime: Arc<R>, context: C) -> Self {
-        Client { runtime, context }
+        Client {
+            runtime,
+            context,
+            retry_policy: None,
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: Option<RetryPolicy>) -> Self {
+        self.retry_policy = retry_policy;
+        self
     }
 
     pub fn context(&self) -> &C {
         &self.context
     }
+
+    pub fn runtime(&self) -> &R {
+        &self.runtime
+    }
+}
+
+// Implemented by hand so that the runtime, which is shared rather than copied, needn't be `Clone`
+impl<R, C: Clone> Clone for Client<R, C> {
+    fn clone(&self) -> Self {
+        Client {
+            runtime: self.runtime.clone(),
+            context: self.context.clone(),
+            retry_policy: self.retry_policy.clone(),
+        }
+    }
 }
 
 impl<R: Runtime, C: ActionContext> Client<R, C> {
@@ -54,6 +115,58 @@ impl<R: Runtime, C: ActionContext> Client<R, C> {
         self.send_message(MessageContentInitial::Text(TextContent { text }))
     }
 
+    pub fn edit_message(
+        &self,
+        message_id: MessageId,
+        content: MessageContentInitial,
+    ) -> EditMessageBuilder<R, C> {
+        EditMessageBuilder::new(self, message_id, content)
+    }
+
+    pub fn delete_messages(&self, message_ids: Vec<MessageId>) -> DeleteMessagesBuilder<R, C> {
+        DeleteMessagesBuilder::new(self, message_ids)
+    }
+
+    pub fn add_reaction(
+        &self,
+        message_id: MessageId,
+        reaction: String,
+    ) -> AddReactionBuilder<R, C> {
+        AddReactionBuilder::new(self, message_id, reaction)
+    }
+
+    pub fn remove_reaction(
+        &self,
+        message_id: MessageId,
+        reaction: String,
+    ) -> RemoveReactionBuilder<R, C> {
+        RemoveReactionBuilder::new(self, message_id, reaction)
+    }
+
+    pub fn pin_message(&self, message_index: MessageIndex) -> PinMessageBuilder<R, C> {
+        PinMessageBuilder::new(self, message_index)
+    }
+
+    pub fn unpin_message(&self, message_index: MessageIndex) -> UnpinMessageBuilder<R, C> {
+        UnpinMessageBuilder::new(self, message_index)
+    }
+
+    pub fn invite_users(&self, user_ids: Vec<UserId>) -> InviteUsersBuilder<R, C> {
+        InviteUsersBuilder::new(self, user_ids)
+    }
+
+    pub fn add_members(&self, user_ids: Vec<UserId>) -> AddMembersBuilder<R, C> {
+        AddMembersBuilder::new(self, user_ids)
+    }
+
+    pub fn remove_user(&self, user_id: UserId) -> RemoveUserBuilder<R, C> {
+        RemoveUserBuilder::new(self, user_id)
+    }
+
+    pub fn change_role(&self, user_id: UserId, new_role: ChatRole) -> ChangeRoleBuilder<R, C> {
+        ChangeRoleBuilder::new(self, user_id, new_role)
+    }
+
     pub fn create_channel(&self, name: String, is_public: bool) -> CreateChannelBuilder<R, C> {
         CreateChannelBuilder::new(self, name, is_public)
     }
@@ -62,6 +175,10 @@ impl<R: Runtime, C: ActionContext> Client<R, C> {
         DeleteChannelBuilder::new(self, channel_id)
     }
 
+    pub fn update_chat(&self) -> UpdateChatBuilder<R, C> {
+        UpdateChatBuilder::new(self)
+    }
+
     pub fn chat_details(&self) -> ChatDetailsBuilder<R, C> {
         ChatDetailsBuilder::new(self)
     }
@@ -69,4 +186,24 @@ impl<R: Runtime, C: ActionContext> Client<R, C> {
     pub fn chat_events(&self, events: EventsSelectionCriteria) -> ChatEventsBuilder<R, C> {
         ChatEventsBuilder::new(self, events)
     }
+
+    pub fn chat_members(&self) -> ChatMembersBuilder<R, C> {
+        ChatMembersBuilder::new(self)
+    }
+}
+
+// Actions targeting a community itself require community permissions whereas actions targeting a
+// group or channel require chat permissions
+fn chat_or_community_permissions(
+    scope: ActionScope,
+    channel_id: Option<ChannelId>,
+    chat_permission: ChatPermission,
+    community_permission: Option<CommunityPermission>,
+) -> BotPermissions {
+    match (scope, channel_id, community_permission) {
+        (ActionScope::Community(_), None, Some(permission)) => {
+            BotPermissions::from_community_permission(permission)
+        }
+        _ => BotPermissions::from_chat_permission(chat_permission),
+    }
 }
@@ -0,0 +1,69 @@
+use super::{chat_or_community_permissions, Client};
+use crate::oc_api::actions::add_members::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{ActionContext, BotPermissions, CanisterId, ChannelId, ChatPermission, UserId};
+use std::sync::Arc;
+
+pub struct AddMembersBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    user_ids: Vec<UserId>,
+    channel_id: Option<ChannelId>,
+}
+
+impl<'c, R: Runtime, C: ActionContext> AddMembersBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>, user_ids: Vec<UserId>) -> Self {
+        let channel_id = client.context.channel_id();
+
+        AddMembersBuilder {
+            client,
+            user_ids,
+            channel_id,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for AddMembersBuilder<'_, R, C> {
+    type Action = AddMembersAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        Some(chat_or_community_permissions(
+            self.client.context.scope(),
+            self.channel_id,
+            ChatPermission::AddMembers,
+            None,
+        ))
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.client.context.granted_permissions()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            user_ids: self.user_ids,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use super::Client;
+use crate::oc_api::actions::add_reaction::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{
+    ActionContext, BotPermissions, CanisterId, ChannelId, ChatPermission, MessageId, MessageIndex,
+};
+use std::sync::Arc;
+
+pub struct AddReactionBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    message_id: MessageId,
+    reaction: String,
+    channel_id: Option<ChannelId>,
+    thread: Option<MessageIndex>,
+}
+
+impl<'c, R: Runtime, C: ActionContext> AddReactionBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>, message_id: MessageId, reaction: String) -> Self {
+        let channel_id = client.context.channel_id();
+        let thread = client.context.thread();
+
+        AddReactionBuilder {
+            client,
+            message_id,
+            reaction,
+            channel_id,
+            thread,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+
+    // By default the message is assumed to be in the thread the command was executed in (if any)
+    pub fn with_thread(mut self, thread: Option<MessageIndex>) -> Self {
+        self.thread = thread;
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for AddReactionBuilder<'_, R, C> {
+    type Action = AddReactionAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        Some(BotPermissions::from_chat_permission(
+            ChatPermission::ReactToMessages,
+        ))
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.client.context.granted_permissions()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            thread: self.thread,
+            message_id: self.message_id,
+            reaction: self.reaction,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
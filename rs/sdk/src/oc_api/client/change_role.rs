@@ -0,0 +1,75 @@
+use super::{chat_or_community_permissions, Client};
+use crate::oc_api::actions::change_role::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{
+    ActionContext, BotPermissions, CanisterId, ChannelId, ChatPermission, ChatRole,
+    CommunityPermission, UserId,
+};
+use std::sync::Arc;
+
+pub struct ChangeRoleBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    user_id: UserId,
+    new_role: ChatRole,
+    channel_id: Option<ChannelId>,
+}
+
+impl<'c, R: Runtime, C: ActionContext> ChangeRoleBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>, user_id: UserId, new_role: ChatRole) -> Self {
+        let channel_id = client.context.channel_id();
+
+        ChangeRoleBuilder {
+            client,
+            user_id,
+            new_role,
+            channel_id,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for ChangeRoleBuilder<'_, R, C> {
+    type Action = ChangeRoleAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        Some(chat_or_community_permissions(
+            self.client.context.scope(),
+            self.channel_id,
+            ChatPermission::ChangeRoles,
+            Some(CommunityPermission::ChangeRoles),
+        ))
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.client.context.granted_permissions()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            user_id: self.user_id,
+            new_role: self.new_role,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use super::Client;
 use crate::oc_api::actions::chat_details::*;
 use crate::oc_api::actions::ActionArgsBuilder;
-use crate::oc_api::Runtime;
+use crate::oc_api::{RetryPolicy, Runtime};
 use crate::types::{ActionContext, CanisterId, ChannelId};
 use std::sync::Arc;
 
@@ -37,6 +37,10 @@ impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for ChatDetailsBuilder<'
         self.client.context.api_gateway()
     }
 
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
     fn into_args(self) -> Args {
         Args {
             auth_token: self.client.context.auth_token().clone(),
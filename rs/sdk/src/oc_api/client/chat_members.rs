@@ -0,0 +1,82 @@
+use super::Client;
+use crate::oc_api::actions::chat_members::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{ActionContext, BotPermissions, CanisterId, ChannelId, ChatPermission};
+use std::sync::Arc;
+
+const DEFAULT_MAX_RESULTS: u32 = 100;
+
+pub struct ChatMembersBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    channel_id: Option<ChannelId>,
+    offset: u32,
+    max_results: u32,
+}
+
+impl<'c, R: Runtime, C: ActionContext> ChatMembersBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>) -> Self {
+        let channel_id = client.context.channel_id();
+
+        ChatMembersBuilder {
+            client,
+            channel_id,
+            offset: 0,
+            max_results: DEFAULT_MAX_RESULTS,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+
+    // Use `ChatMembersPage::next_offset` to page through the members
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_max_results(mut self, max_results: u32) -> Self {
+        self.max_results = max_results;
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for ChatMembersBuilder<'_, R, C> {
+    type Action = ChatMembersAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        Some(BotPermissions::from_chat_permission(
+            ChatPermission::ReadMembership,
+        ))
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.client.context.granted_permissions()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            offset: self.offset,
+            max_results: self.max_results,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
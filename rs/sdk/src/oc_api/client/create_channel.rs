@@ -1,7 +1,7 @@
 use super::Client;
 use crate::oc_api::actions::create_channel::*;
 use crate::oc_api::actions::ActionArgsBuilder;
-use crate::oc_api::Runtime;
+use crate::oc_api::{RetryPolicy, Runtime};
 use crate::types::{
     AccessGateConfig, ActionContext, CanisterId, ChatPermissions, Document, Milliseconds, Rules,
 };
@@ -103,6 +103,15 @@ impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for CreateChannelBuilder
         self.client.context.api_gateway()
     }
 
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    // Each successful call creates another channel
+    fn is_idempotent(&self) -> bool {
+        false
+    }
+
     fn into_args(self) -> Args {
         Args {
             auth_token: self.client.context.auth_token().clone(),
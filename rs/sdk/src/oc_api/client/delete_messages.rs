@@ -0,0 +1,66 @@
+use super::Client;
+use crate::oc_api::actions::delete_messages::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{ActionContext, CanisterId, ChannelId, MessageId, MessageIndex};
+use std::sync::Arc;
+
+pub struct DeleteMessagesBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    message_ids: Vec<MessageId>,
+    channel_id: Option<ChannelId>,
+    thread: Option<MessageIndex>,
+}
+
+impl<'c, R: Runtime, C: ActionContext> DeleteMessagesBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>, message_ids: Vec<MessageId>) -> Self {
+        let channel_id = client.context.channel_id();
+        let thread = client.context.thread();
+
+        DeleteMessagesBuilder {
+            client,
+            message_ids,
+            channel_id,
+            thread,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+
+    // By default the messages are assumed to be in the thread the command was executed in (if any)
+    pub fn with_thread(mut self, thread: Option<MessageIndex>) -> Self {
+        self.thread = thread;
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for DeleteMessagesBuilder<'_, R, C> {
+    type Action = DeleteMessagesAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            thread: self.thread,
+            message_ids: self.message_ids,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::Client;
+use crate::oc_api::actions::edit_message::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{
+    ActionContext, CanisterId, ChannelId, MessageContentInitial, MessageId, MessageIndex,
+};
+use std::sync::Arc;
+
+pub struct EditMessageBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    message_id: MessageId,
+    content: MessageContentInitial,
+    channel_id: Option<ChannelId>,
+    thread: Option<MessageIndex>,
+    block_level_markdown: bool,
+    finalised: bool,
+}
+
+impl<'c, R: Runtime, C: ActionContext> EditMessageBuilder<'c, R, C> {
+    pub fn new(
+        client: &'c Client<R, C>,
+        message_id: MessageId,
+        content: MessageContentInitial,
+    ) -> Self {
+        let channel_id = client.context.channel_id();
+        let thread = client.context.thread();
+
+        EditMessageBuilder {
+            client,
+            message_id,
+            content,
+            channel_id,
+            thread,
+            block_level_markdown: false,
+            finalised: true,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+
+    // By default the message is assumed to be in the thread the command was executed in (if any)
+    pub fn with_thread(mut self, thread: Option<MessageIndex>) -> Self {
+        self.thread = thread;
+        self
+    }
+
+    pub fn with_block_level_markdown(mut self, block_level_markdown: bool) -> Self {
+        self.block_level_markdown = block_level_markdown;
+        self
+    }
+
+    // Set this to false to keep editing the message, eg. when streaming a response
+    pub fn with_finalised(mut self, finalised: bool) -> Self {
+        self.finalised = finalised;
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for EditMessageBuilder<'_, R, C> {
+    type Action = EditMessageAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            thread: self.thread,
+            message_id: self.message_id,
+            content: self.content,
+            block_level_markdown: self.block_level_markdown,
+            finalised: self.finalised,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
@@ -1,9 +1,9 @@
 use crate::api::command::Message;
 use crate::oc_api::actions::send_message::*;
 use crate::oc_api::actions::ActionArgsBuilder;
-use crate::oc_api::Runtime;
+use crate::oc_api::{RetryPolicy, Runtime};
 use crate::types::{ActionContext, CallResult};
-use crate::types::{CanisterId, ChannelId, MessageContentInitial, MessageId};
+use crate::types::{CanisterId, ChannelId, MessageContentInitial, MessageId, MessageIndex, UserId};
 use std::sync::Arc;
 
 use super::Client;
@@ -13,6 +13,10 @@ pub struct SendMessageBuilder<'c, R, C> {
     content: MessageContentInitial,
     channel_id: Option<ChannelId>,
     message_id: Option<MessageId>,
+    thread: Option<MessageIndex>,
+    replies_to: Option<MessageIndex>,
+    mentioned: Vec<UserId>,
+    forwarding: bool,
     block_level_markdown: bool,
     finalised: bool,
 }
@@ -21,12 +25,17 @@ impl<'c, R: Runtime, C: ActionContext> SendMessageBuilder<'c, R, C> {
     pub fn new(client: &'c Client<R, C>, content: MessageContentInitial) -> Self {
         let channel_id = client.context.channel_id();
         let message_id = client.context.message_id();
+        let thread = client.context.thread();
 
         Self {
             client,
             content,
             channel_id,
             message_id,
+            thread,
+            replies_to: None,
+            mentioned: Vec::new(),
+            forwarding: false,
             block_level_markdown: false,
             finalised: true,
         }
@@ -49,6 +58,28 @@ impl<'c, R: Runtime, C: ActionContext> SendMessageBuilder<'c, R, C> {
         self
     }
 
+    // By default the message is sent to the thread the command was executed in (if any)
+    // Set this to `None` to send the message to the main chat instead
+    pub fn with_thread(mut self, thread: Option<MessageIndex>) -> Self {
+        self.thread = thread;
+        self
+    }
+
+    pub fn with_replies_to(mut self, message_index: MessageIndex) -> Self {
+        self.replies_to = Some(message_index);
+        self
+    }
+
+    pub fn with_mentioned(mut self, mentioned: Vec<UserId>) -> Self {
+        self.mentioned = mentioned;
+        self
+    }
+
+    pub fn with_forwarding(mut self, forwarding: bool) -> Self {
+        self.forwarding = forwarding;
+        self
+    }
+
     pub fn with_block_level_markdown(mut self, block_level_markdown: bool) -> Self {
         self.block_level_markdown = block_level_markdown;
         self
@@ -89,11 +120,24 @@ impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for SendMessageBuilder<'
         self.client.context.api_gateway()
     }
 
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    // Only a message with a stable `message_id` is safe to send again
+    fn is_idempotent(&self) -> bool {
+        self.message_id.is_some()
+    }
+
     fn into_args(self) -> Args {
         Args {
             content: self.content,
             channel_id: self.channel_id,
             message_id: self.message_id,
+            thread: self.thread,
+            replies_to: self.replies_to,
+            mentioned: self.mentioned,
+            forwarding: self.forwarding,
             block_level_markdown: self.block_level_markdown,
             finalised: self.finalised,
             auth_token: self.client.context.auth_token().clone(),
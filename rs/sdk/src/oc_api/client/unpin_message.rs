@@ -0,0 +1,68 @@
+use super::Client;
+use crate::oc_api::actions::unpin_message::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{
+    ActionContext, BotPermissions, CanisterId, ChannelId, ChatPermission, MessageIndex,
+};
+use std::sync::Arc;
+
+pub struct UnpinMessageBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    message_index: MessageIndex,
+    channel_id: Option<ChannelId>,
+}
+
+impl<'c, R: Runtime, C: ActionContext> UnpinMessageBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>, message_index: MessageIndex) -> Self {
+        let channel_id = client.context.channel_id();
+
+        UnpinMessageBuilder {
+            client,
+            message_index,
+            channel_id,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for UnpinMessageBuilder<'_, R, C> {
+    type Action = UnpinMessageAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        Some(BotPermissions::from_chat_permission(
+            ChatPermission::PinMessages,
+        ))
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.client.context.granted_permissions()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            message_index: self.message_index,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::{chat_or_community_permissions, Client};
+use crate::oc_api::actions::update_chat::*;
+use crate::oc_api::actions::ActionArgsBuilder;
+use crate::oc_api::{RetryPolicy, Runtime};
+use crate::types::{
+    AccessGateConfig, ActionContext, BotPermissions, CanisterId, ChannelId, ChatPermission,
+    ChatPermissions, CommunityPermission, Document, Milliseconds, OptionUpdate, Rules,
+};
+use std::sync::Arc;
+
+pub struct UpdateChatBuilder<'c, R, C> {
+    client: &'c Client<R, C>,
+    channel_id: Option<ChannelId>,
+    name: Option<String>,
+    description: Option<String>,
+    rules: Option<Rules>,
+    avatar: OptionUpdate<Document>,
+    permissions: Option<ChatPermissions>,
+    events_ttl: OptionUpdate<Milliseconds>,
+    gate_config: OptionUpdate<AccessGateConfig>,
+    external_url: OptionUpdate<String>,
+}
+
+impl<'c, R: Runtime, C: ActionContext> UpdateChatBuilder<'c, R, C> {
+    pub fn new(client: &'c Client<R, C>) -> Self {
+        let channel_id = client.context.channel_id();
+
+        // Only the fields which are explicitly set are updated
+        UpdateChatBuilder {
+            client,
+            channel_id,
+            name: None,
+            description: None,
+            rules: None,
+            avatar: OptionUpdate::NoChange,
+            permissions: None,
+            events_ttl: OptionUpdate::NoChange,
+            gate_config: OptionUpdate::NoChange,
+            external_url: OptionUpdate::NoChange,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        if self.channel_id.is_none() {
+            self.channel_id = Some(channel_id);
+        }
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    // Pass None to remove the avatar
+    pub fn with_avatar(mut self, avatar: Option<Document>) -> Self {
+        self.avatar = OptionUpdate::from_update(avatar);
+        self
+    }
+
+    pub fn with_permissions(mut self, permissions: ChatPermissions) -> Self {
+        self.permissions = Some(permissions);
+        self
+    }
+
+    // Pass None to disable disappearing messages
+    pub fn with_disappearing_messages(mut self, events_ttl: Option<Milliseconds>) -> Self {
+        self.events_ttl = OptionUpdate::from_update(events_ttl);
+        self
+    }
+
+    // Pass None to remove the access gate
+    pub fn with_gate_config(mut self, gate_config: Option<AccessGateConfig>) -> Self {
+        self.gate_config = OptionUpdate::from_update(gate_config);
+        self
+    }
+
+    // Pass None to remove the external url
+    pub fn with_external_url(mut self, external_url: Option<String>) -> Self {
+        self.external_url = OptionUpdate::from_update(external_url);
+        self
+    }
+}
+
+impl<R: Runtime, C: ActionContext> ActionArgsBuilder<R> for UpdateChatBuilder<'_, R, C> {
+    type Action = UpdateChatAction;
+
+    fn runtime(&self) -> Arc<R> {
+        self.client.runtime.clone()
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.client.context.api_gateway()
+    }
+
+    fn retry_policy(&self) -> Option<RetryPolicy> {
+        self.client.retry_policy.clone()
+    }
+
+    fn required_permissions(&self) -> Option<BotPermissions> {
+        Some(chat_or_community_permissions(
+            self.client.context.scope(),
+            self.channel_id,
+            ChatPermission::UpdateGroup,
+            Some(CommunityPermission::UpdateDetails),
+        ))
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        self.client.context.granted_permissions()
+    }
+
+    fn into_args(self) -> Args {
+        Args {
+            channel_id: self.channel_id,
+            name: self.name,
+            description: self.description,
+            rules: self.rules,
+            avatar: self.avatar,
+            permissions: self.permissions,
+            events_ttl: self.events_ttl,
+            gate_config: self.gate_config,
+            external_url: self.external_url,
+            auth_token: self.client.context.auth_token().clone(),
+        }
+    }
+}
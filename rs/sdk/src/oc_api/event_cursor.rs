@@ -0,0 +1,202 @@
+use crate::oc_api::actions::chat_events::{
+    EventsPageArgs, EventsResponse, EventsSelectionCriteria,
+};
+use crate::oc_api::actions::{ActionArgsBuilder, ActionResponse};
+use crate::oc_api::client::Client;
+use crate::oc_api::Runtime;
+use crate::types::{ActionContext, ChannelId, ChatEvent, EventIndex, EventWrapper, OcApiError};
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_EVENTS: u32 = 100;
+
+/// Follows the events of a chat, remembering which events have already been seen.
+///
+/// Each call to [`EventCursor::poll`] fetches the next page of events after the last one returned,
+/// skipping over events which have expired or which the bot is not authorized to see. The cursor
+/// can be serialized so that a bot can persist it (eg. in stable memory) and carry on from where
+/// it left off.
+///
+/// ```ignore
+/// let mut cursor = EventCursor::new(0);
+///
+/// loop {
+///     let next_index = cursor.next_index();
+///
+///     for event in cursor.poll(&oc_client).await? {
+///         handle(event);
+///     }
+///
+///     // Stop once caught up, or if the poll didn't move the cursor on so would never catch up
+///     if cursor.is_caught_up() || cursor.next_index() == next_index {
+///         break;
+///     }
+/// }
+/// ```
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EventCursor {
+    next_index: EventIndex,
+    #[serde(default)]
+    latest_event_index: Option<EventIndex>,
+    #[serde(default)]
+    channel_id: Option<ChannelId>,
+    #[serde(default = "default_max_events")]
+    max_events: u32,
+}
+
+fn default_max_events() -> u32 {
+    DEFAULT_MAX_EVENTS
+}
+
+impl EventCursor {
+    pub fn new(start_index: EventIndex) -> Self {
+        EventCursor {
+            next_index: start_index,
+            latest_event_index: None,
+            channel_id: None,
+            max_events: DEFAULT_MAX_EVENTS,
+        }
+    }
+
+    // This only takes effect for community scope
+    pub fn with_channel_id(mut self, channel_id: ChannelId) -> Self {
+        self.channel_id = Some(channel_id);
+        self
+    }
+
+    pub fn with_max_events(mut self, max_events: u32) -> Self {
+        self.max_events = max_events;
+        self
+    }
+
+    pub fn next_index(&self) -> EventIndex {
+        self.next_index
+    }
+
+    // True if the last poll reached the latest event in the chat
+    pub fn is_caught_up(&self) -> bool {
+        self.latest_event_index
+            .is_some_and(|latest| self.next_index > latest)
+    }
+
+    pub fn criteria(&self) -> EventsSelectionCriteria {
+        EventsSelectionCriteria::Page(EventsPageArgs {
+            start_index: self.next_index,
+            ascending: true,
+            max_messages: self.max_events,
+            max_events: self.max_events,
+        })
+    }
+
+    pub async fn poll<R: Runtime, C: ActionContext>(
+        &mut self,
+        client: &Client<R, C>,
+    ) -> Result<Vec<EventWrapper<ChatEvent>>, OcApiError> {
+        let mut builder = client.chat_events(self.criteria());
+        if let Some(channel_id) = self.channel_id {
+            builder = builder.with_channel_id(channel_id);
+        }
+
+        let response = builder.execute_async().await?.into_result()?;
+
+        Ok(self.advance(response))
+    }
+
+    // Moves the cursor past the events in the response, returning those not seen before in order
+    pub fn advance(&mut self, response: EventsResponse) -> Vec<EventWrapper<ChatEvent>> {
+        let mut events: Vec<_> = response
+            .events
+            .into_iter()
+            .filter(|e| e.index >= self.next_index)
+            .collect();
+
+        events.sort_by_key(|e| e.index);
+        events.dedup_by_key(|e| e.index);
+
+        let mut next_index = events
+            .last()
+            .map_or(self.next_index, |e| e.index.saturating_add(1));
+
+        // Step over any events which will never be returned
+        loop {
+            if response.unauthorized.contains(&next_index) {
+                next_index = next_index.saturating_add(1);
+            } else if let Some((_, to)) = response
+                .expired_event_ranges
+                .iter()
+                .find(|(from, to)| *from <= next_index && next_index <= *to)
+            {
+                next_index = to.saturating_add(1);
+            } else {
+                break;
+            }
+        }
+
+        self.next_index = next_index;
+        self.latest_event_index = Some(response.latest_event_index);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(index: EventIndex) -> EventWrapper<ChatEvent> {
+        EventWrapper {
+            index,
+            timestamp: 0,
+            correlation_id: 0,
+            expires_at: None,
+            event: ChatEvent::Empty,
+        }
+    }
+
+    fn response(
+        events: Vec<EventIndex>,
+        unauthorized: Vec<EventIndex>,
+        expired_event_ranges: Vec<(EventIndex, EventIndex)>,
+        latest_event_index: EventIndex,
+    ) -> EventsResponse {
+        EventsResponse {
+            events: events.into_iter().map(event).collect(),
+            unauthorized,
+            expired_event_ranges,
+            expired_message_ranges: Vec::new(),
+            latest_event_index,
+            chat_last_updated: 0,
+        }
+    }
+
+    #[test]
+    fn advance_skips_seen_expired_and_unauthorized_events() {
+        let mut cursor = EventCursor::new(3);
+
+        let events = cursor.advance(response(vec![5, 2, 4, 3, 4], vec![6], vec![(7, 9)], 12));
+        assert_eq!(
+            events.iter().map(|e| e.index).collect::<Vec<_>>(),
+            [3, 4, 5]
+        );
+        assert_eq!(cursor.next_index(), 10);
+        assert!(!cursor.is_caught_up());
+
+        let events = cursor.advance(response(vec![10, 11, 12], vec![], vec![], 12));
+        assert_eq!(events.len(), 3);
+        assert!(cursor.is_caught_up());
+    }
+
+    #[test]
+    fn advance_with_no_events_only_skips_expired_ranges() {
+        let mut cursor = EventCursor::new(0);
+
+        assert!(cursor
+            .advance(response(vec![], vec![], vec![(0, 4)], 8))
+            .is_empty());
+        assert_eq!(cursor.next_index(), 5);
+
+        assert!(cursor
+            .advance(response(vec![], vec![], vec![], 8))
+            .is_empty());
+        assert_eq!(cursor.next_index(), 5);
+    }
+}
@@ -1,7 +1,10 @@
+mod retry;
 mod runtime;
 
 pub mod actions;
 pub mod client;
+pub mod event_cursor;
 pub mod get_access_token;
 
+pub use retry::RetryPolicy;
 pub use runtime::Runtime;
@@ -0,0 +1,96 @@
+use crate::types::Milliseconds;
+use std::time::Duration;
+
+/// Controls how many times an action is attempted and how long to wait between attempts.
+///
+/// Only failures to make the call which may succeed if retried (transport errors, timeouts and
+/// transient rejections, see [`crate::types::OcApiError::is_retryable`]) are retried. Failure
+/// responses returned by OpenChat are passed straight back to the caller. Timeouts are only retried
+/// for actions which are safe to repeat, so a message is only resent after a timeout if it has a
+/// `message_id`.
+///
+/// The wait between attempts is scheduled through the [`crate::oc_api::Runtime`], so uses an IC
+/// timer in canisters. To retry straight away set the initial backoff to 0.
+///
+/// ```ignore
+/// let oc_client_factory = ClientFactory::new(runtime).with_retry_policy(RetryPolicy::default());
+///
+/// oc_client
+///     .send_text_message(text)
+///     .with_retry(RetryPolicy::new(5).with_initial_backoff(500))
+///     .execute_async()
+///     .await
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Milliseconds,
+    max_backoff: Milliseconds,
+    multiplier: u32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: 1_000,
+            max_backoff: 30_000,
+            multiplier: 2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_initial_backoff(mut self, initial_backoff: Milliseconds) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, max_backoff: Milliseconds) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    // The time to wait after the given (1-based) failed attempt before trying again
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = (self.multiplier as u64).saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        Duration::from_millis(backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max() {
+        let policy = RetryPolicy::new(10)
+            .with_initial_backoff(100)
+            .with_max_backoff(1_000);
+
+        let backoffs: Vec<_> = (1..=6).map(|a| policy.backoff(a).as_millis()).collect();
+
+        assert_eq!(backoffs, [100, 200, 400, 800, 1_000, 1_000]);
+    }
+}
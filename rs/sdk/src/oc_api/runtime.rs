@@ -1,6 +1,7 @@
 use crate::types::{CallResult, CanisterId, TimestampMillis};
 use candid::utils::{ArgumentDecoder, ArgumentEncoder};
 use std::future::Future;
+use std::time::Duration;
 
 pub trait Runtime: Send + Sync + 'static {
     fn call_canister<A: ArgumentEncoder + Send, R: for<'a> ArgumentDecoder<'a>>(
@@ -12,6 +13,8 @@ pub trait Runtime: Send + Sync + 'static {
 
     fn spawn<F: Future<Output = ()> + Send + 'static>(&self, f: F);
 
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+
     fn now(&self) -> TimestampMillis;
 
     fn is_canister(&self) -> bool;
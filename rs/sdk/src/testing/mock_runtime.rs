@@ -0,0 +1,248 @@
+use crate::oc_api::actions::ActionDef;
+use crate::oc_api::Runtime;
+use crate::types::{CallResult, CanisterId, OcApiError, TimestampMillis};
+use candid::utils::{ArgumentDecoder, ArgumentEncoder};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Duration;
+
+// Most futures complete within a few polls when run against the mock so if a future is still
+// pending after this many it is never going to complete
+const MAX_POLLS: usize = 1_000;
+
+type Handler = Box<dyn FnMut(&[u8]) -> CallResult<Vec<u8>> + Send>;
+type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// A `Runtime` which never leaves the process, for unit testing bots without a replica.
+///
+/// Every call to `call_canister` is recorded and answered by the handler registered for the
+/// method being called, which can either return a scripted response (`respond_with`) or act as a
+/// stateful fake of the OpenChat gateway (`on`). Calls with no handler fail with a transport
+/// error. The clock only moves when told to, and `sleep` advances it rather than waiting.
+///
+/// ```ignore
+/// let runtime = Arc::new(MockRuntime::new());
+/// runtime.respond_with::<SendMessageAction>(send_message::Response::Success(result));
+///
+/// let client = Client::new(runtime.clone(), context);
+/// runtime.block_on(Roll.execute(client)).unwrap();
+///
+/// let sent = runtime.calls_to::<SendMessageAction>();
+/// assert_eq!(sent.len(), 1);
+/// ```
+pub struct MockRuntime {
+    now: AtomicU64,
+    is_canister: bool,
+    calls: Mutex<Vec<RecordedCall>>,
+    handlers: Mutex<HashMap<String, Handler>>,
+    tasks: Mutex<Vec<Task>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RecordedCall {
+    pub canister_id: CanisterId,
+    pub method_name: String,
+    pub args: Vec<u8>,
+    pub timestamp: TimestampMillis,
+}
+
+impl Default for MockRuntime {
+    fn default() -> Self {
+        MockRuntime::new()
+    }
+}
+
+impl MockRuntime {
+    pub fn new() -> Self {
+        MockRuntime {
+            now: AtomicU64::new(0),
+            is_canister: false,
+            calls: Mutex::default(),
+            handlers: Mutex::default(),
+            tasks: Mutex::default(),
+        }
+    }
+
+    // Handlers are registered against the method names used by this type of runtime so this
+    // must be set before registering any
+    pub fn with_is_canister(mut self, is_canister: bool) -> Self {
+        self.is_canister = is_canister;
+        self
+    }
+
+    pub fn with_now(self, now: TimestampMillis) -> Self {
+        self.set_now(now);
+        self
+    }
+
+    pub fn set_now(&self, now: TimestampMillis) {
+        self.now.store(now, Ordering::SeqCst);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now
+            .fetch_add(duration.as_millis() as TimestampMillis, Ordering::SeqCst);
+    }
+
+    // Every call to the action's method returns `response`
+    pub fn respond_with<A: ActionDef>(&self, response: A::Response) {
+        let response = candid::encode_one(response).expect("Failed to encode response");
+
+        self.register::<A>(Box::new(move |_| Ok(response.clone())));
+    }
+
+    // Every call to the action's method fails with `error`, eg. to test how failures are handled
+    pub fn fail_with<A: ActionDef>(&self, error: OcApiError) {
+        self.register::<A>(Box::new(move |_| Err(error.clone())));
+    }
+
+    // Calls to the action's method are passed to `handler`, which can keep state between calls
+    pub fn on<A, F>(&self, mut handler: F)
+    where
+        A: ActionDef,
+        A::Args: DeserializeOwned,
+        F: FnMut(A::Args) -> CallResult<A::Response> + Send + 'static,
+    {
+        self.register::<A>(Box::new(move |args| {
+            let args = candid::decode_one(args)
+                .map_err(|error| OcApiError::InvalidRequest(error.to_string()))?;
+
+            candid::encode_one(handler(args)?)
+                .map_err(|error| OcApiError::Decode(error.to_string()))
+        }));
+    }
+
+    pub fn calls(&self) -> Vec<RecordedCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    // The decoded args of each call made to the action's method, in the order they were made
+    pub fn calls_to<A: ActionDef>(&self) -> Vec<A::Args>
+    where
+        A::Args: DeserializeOwned,
+    {
+        let method_name = A::method_name(self.is_canister);
+
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|call| call.method_name == method_name)
+            .map(|call| candid::decode_one(&call.args).expect("Failed to decode args"))
+            .collect()
+    }
+
+    pub fn clear_calls(&self) {
+        self.calls.lock().unwrap().clear();
+    }
+
+    // Drives `future` to completion, along with any tasks it spawns
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+
+        for _ in 0..MAX_POLLS {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                self.run_pending_tasks();
+                return output;
+            }
+            self.run_pending_tasks();
+        }
+
+        panic!("Future did not complete after {MAX_POLLS} polls");
+    }
+
+    // Polls each spawned task which has not yet completed. Returns the number still pending.
+    pub fn run_pending_tasks(&self) -> usize {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut cx = Context::from_waker(&waker);
+
+        // Take the tasks so that any spawned while polling don't deadlock
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        let mut pending: Vec<_> = tasks
+            .into_iter()
+            .filter_map(|mut task| task.as_mut().poll(&mut cx).is_pending().then_some(task))
+            .collect();
+
+        let mut tasks = self.tasks.lock().unwrap();
+        pending.append(&mut tasks);
+        *tasks = pending;
+        tasks.len()
+    }
+
+    fn register<A: ActionDef>(&self, handler: Handler) {
+        self.handlers
+            .lock()
+            .unwrap()
+            .insert(A::method_name(self.is_canister).to_string(), handler);
+    }
+
+    fn handle(
+        &self,
+        canister_id: CanisterId,
+        method_name: &str,
+        args: Vec<u8>,
+    ) -> CallResult<Vec<u8>> {
+        let response = match self.handlers.lock().unwrap().get_mut(method_name) {
+            Some(handler) => handler(&args),
+            None => Err(OcApiError::Transport(format!(
+                "No response registered for '{method_name}'"
+            ))),
+        };
+
+        self.calls.lock().unwrap().push(RecordedCall {
+            canister_id,
+            method_name: method_name.to_string(),
+            args,
+            timestamp: self.now(),
+        });
+
+        response
+    }
+}
+
+impl Runtime for MockRuntime {
+    async fn call_canister<A: ArgumentEncoder + Send, R: for<'a> ArgumentDecoder<'a>>(
+        &self,
+        canister_id: CanisterId,
+        method_name: &str,
+        args: A,
+    ) -> CallResult<R> {
+        let args = candid::encode_args(args)
+            .map_err(|error| OcApiError::InvalidRequest(error.to_string()))?;
+
+        let bytes = self.handle(canister_id, method_name, args)?;
+
+        candid::decode_args(&bytes).map_err(|error| OcApiError::Decode(error.to_string()))
+    }
+
+    fn spawn<F: Future<Output = ()> + Send + 'static>(&self, f: F) {
+        self.tasks.lock().unwrap().push(Box::pin(f));
+        self.run_pending_tasks();
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        self.advance(duration);
+        std::future::ready(())
+    }
+
+    fn now(&self) -> TimestampMillis {
+        self.now.load(Ordering::SeqCst)
+    }
+
+    fn is_canister(&self) -> bool {
+        self.is_canister
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
@@ -0,0 +1,5 @@
+mod mock_runtime;
+mod token_issuer;
+
+pub use mock_runtime::{MockRuntime, RecordedCall};
+pub use token_issuer::TestTokenIssuer;
@@ -0,0 +1,261 @@
+use crate::api::command::Command;
+use crate::jwt::{self, Claims, PublicKey};
+use crate::types::{
+    ActionScope, BotActionByApiKeyClaims, BotActionByCommandClaims, BotActionByEventClaims,
+    BotCommandScope, BotPermissions, CanisterId, ChatEvent, EventWrapper, TimestampMillis, UserId,
+    BOT_ACTION_BY_API_KEY_CLAIM_TYPE, BOT_ACTION_BY_COMMAND_CLAIM_TYPE,
+    BOT_ACTION_BY_EVENT_CLAIM_TYPE,
+};
+use candid::Principal;
+use p256::ecdsa::SigningKey;
+use p256::pkcs8::{DecodePrivateKey, EncodePrivateKey, EncodePublicKey, LineEnding};
+
+/// Mints tokens in the same format as OpenChat, signed with a freshly generated P-256 key.
+///
+/// Configure the bot under test with `public_key_pem` in place of OpenChat's public key and it
+/// will accept the tokens minted here, eg. when calling its `/execute_command` endpoint.
+///
+/// ```ignore
+/// let issuer = TestTokenIssuer::new();
+/// let jwt = issuer.command_jwt(command, scope, BotPermissions::text_only(), now + 60_000);
+///
+/// let context = BotCommandContext::parse(jwt, issuer.public_key(), now)?;
+/// ```
+pub struct TestTokenIssuer {
+    private_key_pem: String,
+    public_key_pem: String,
+    public_key: PublicKey,
+    kid: Option<String>,
+    bot_id: UserId,
+    api_gateway: CanisterId,
+}
+
+impl Default for TestTokenIssuer {
+    fn default() -> Self {
+        TestTokenIssuer::new()
+    }
+}
+
+impl TestTokenIssuer {
+    pub fn new() -> Self {
+        TestTokenIssuer::from_signing_key(SigningKey::random(&mut rand::thread_rng()))
+    }
+
+    // Allows the same key to be used across runs so that the bot under test only needs to be
+    // configured with the public key once
+    pub fn from_private_key_pem(private_key_pem: &str) -> Result<Self, p256::pkcs8::Error> {
+        SigningKey::from_pkcs8_pem(private_key_pem).map(TestTokenIssuer::from_signing_key)
+    }
+
+    fn from_signing_key(signing_key: SigningKey) -> Self {
+        let private_key_pem = signing_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .expect("Failed to encode private key")
+            .to_string();
+        let public_key_pem = signing_key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .expect("Failed to encode public key");
+        let public_key = PublicKey::from_pem(&public_key_pem).expect("Failed to parse public key");
+
+        TestTokenIssuer {
+            private_key_pem,
+            public_key_pem,
+            public_key,
+            kid: None,
+            bot_id: Principal::anonymous().into(),
+            api_gateway: Principal::anonymous(),
+        }
+    }
+
+    // Names the key in the header of each token, as matched against a bot's `TrustedKeys`
+    pub fn with_kid(mut self, kid: String) -> Self {
+        self.kid = Some(kid);
+        self
+    }
+
+    pub fn with_bot_id(mut self, bot_id: UserId) -> Self {
+        self.bot_id = bot_id;
+        self
+    }
+
+    pub fn with_api_gateway(mut self, api_gateway: CanisterId) -> Self {
+        self.api_gateway = api_gateway;
+        self
+    }
+
+    pub fn private_key_pem(&self) -> &str {
+        &self.private_key_pem
+    }
+
+    pub fn public_key_pem(&self) -> &str {
+        &self.public_key_pem
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn bot_id(&self) -> UserId {
+        self.bot_id
+    }
+
+    pub fn api_gateway(&self) -> CanisterId {
+        self.api_gateway
+    }
+
+    pub fn command_jwt(
+        &self,
+        command: Command,
+        scope: BotCommandScope,
+        granted_permissions: BotPermissions,
+        expiry: TimestampMillis,
+    ) -> String {
+        self.sign(
+            expiry,
+            BOT_ACTION_BY_COMMAND_CLAIM_TYPE,
+            BotActionByCommandClaims {
+                bot_api_gateway: self.api_gateway,
+                bot: self.bot_id,
+                scope,
+                granted_permissions,
+                command,
+            },
+        )
+    }
+
+    pub fn api_key_jwt(
+        &self,
+        scope: ActionScope,
+        granted_permissions: BotPermissions,
+        expiry: TimestampMillis,
+    ) -> String {
+        self.sign(
+            expiry,
+            BOT_ACTION_BY_API_KEY_CLAIM_TYPE,
+            BotActionByApiKeyClaims {
+                bot_api_gateway: self.api_gateway,
+                bot: self.bot_id,
+                scope,
+                granted_permissions,
+            },
+        )
+    }
+
+    pub fn event_jwt(
+        &self,
+        scope: ActionScope,
+        event: EventWrapper<ChatEvent>,
+        granted_permissions: BotPermissions,
+        expiry: TimestampMillis,
+    ) -> String {
+        self.sign(
+            expiry,
+            BOT_ACTION_BY_EVENT_CLAIM_TYPE,
+            BotActionByEventClaims {
+                bot_api_gateway: self.api_gateway,
+                bot: self.bot_id,
+                scope,
+                thread: None,
+                granted_permissions,
+                event,
+            },
+        )
+    }
+
+    fn sign<T: serde::Serialize>(
+        &self,
+        expiry: TimestampMillis,
+        claim_type: &str,
+        custom: T,
+    ) -> String {
+        let claims = Claims::new(expiry, claim_type.to_string(), custom);
+
+        match &self.kid {
+            Some(kid) => jwt::sign_with_kid(&claims, &self.private_key_pem, kid.clone()),
+            None => jwt::sign(&claims, &self.private_key_pem),
+        }
+        .expect("Failed to sign jwt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwt::JwtError;
+    use crate::types::{
+        BotActionChatDetails, BotApiKeyContext, BotCommandContext, Chat, TokenError,
+    };
+
+    const NOW: TimestampMillis = 1_700_000_000_000;
+
+    #[test]
+    fn minted_tokens_are_accepted() {
+        let issuer = TestTokenIssuer::new();
+        let chat = Chat::Group(Principal::anonymous());
+
+        let command_jwt = issuer.command_jwt(
+            Command {
+                name: "roll".to_string(),
+                args: Vec::new(),
+                initiator: Principal::anonymous().into(),
+                meta: None,
+            },
+            BotCommandScope::Chat(BotActionChatDetails {
+                chat,
+                thread: None,
+                message_id: 1.into(),
+                user_message_id: None,
+            }),
+            BotPermissions::text_only(),
+            NOW + 60_000,
+        );
+
+        let context = BotCommandContext::parse(command_jwt, issuer.public_key(), NOW).unwrap();
+        assert_eq!(context.command.name, "roll");
+        assert_eq!(context.granted_permissions, BotPermissions::text_only());
+
+        let api_key_jwt = issuer.api_key_jwt(
+            ActionScope::Chat(chat),
+            BotPermissions::text_only(),
+            NOW + 60_000,
+        );
+
+        let context = BotApiKeyContext::parse_jwt(api_key_jwt, issuer.public_key(), NOW).unwrap();
+        assert_eq!(context.scope, ActionScope::Chat(chat));
+    }
+
+    #[test]
+    fn expired_and_foreign_tokens_are_rejected() {
+        let issuer = TestTokenIssuer::new();
+        let other_issuer = TestTokenIssuer::new();
+        let scope = ActionScope::Community(Principal::anonymous());
+
+        let expired = issuer.api_key_jwt(scope, BotPermissions::default(), NOW - 60_000);
+        assert!(matches!(
+            BotApiKeyContext::parse_jwt(expired, issuer.public_key(), NOW),
+            Err(TokenError::Expired)
+        ));
+
+        // There is no leeway unless one is asked for. Expiries are in whole seconds, so this token
+        // expired a second ago.
+        let just_expired = issuer.api_key_jwt(scope, BotPermissions::default(), NOW - 1_000);
+        assert!(matches!(
+            BotApiKeyContext::parse_jwt(just_expired.clone(), issuer.public_key(), NOW),
+            Err(TokenError::Expired)
+        ));
+        assert!(BotApiKeyContext::parse_jwt_with_leeway(
+            just_expired,
+            issuer.public_key(),
+            NOW,
+            1_001
+        )
+        .is_ok());
+
+        let valid = issuer.api_key_jwt(scope, BotPermissions::default(), NOW + 60_000);
+        assert!(matches!(
+            BotApiKeyContext::parse_jwt(valid, other_issuer.public_key(), NOW),
+            Err(TokenError::Invalid(JwtError::InvalidSignature))
+        ));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::jwt::{self, JwtError, JwtVerifier, PublicKey};
+use crate::types::{Milliseconds, TimestampMillis};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The set of OpenChat public keys a bot accepts tokens signed by.
+///
+/// Holding more than one key allows OpenChat's key to be rotated without downtime: the new key is
+/// added ahead of the rotation and the old key retired once OpenChat has stopped using it. If a
+/// token's header names its key (`kid`) then only the matching key is tried, otherwise each key is
+/// tried in turn.
+///
+/// Each key is parsed once, when it is added or deserialized, rather than for every token.
+///
+/// Deserializes from either a list of keys or a single PEM, so a bot which previously stored or
+/// was configured with a single `oc_public_key` string can switch to this type as is.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrustedKeys {
+    keys: Vec<TrustedKey>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TrustedKey {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(rename = "public_key_pem")]
+    pub public_key: PublicKey,
+}
+
+impl TrustedKeys {
+    pub fn new(public_key: PublicKey) -> TrustedKeys {
+        TrustedKeys {
+            keys: vec![TrustedKey {
+                kid: None,
+                public_key,
+            }],
+        }
+    }
+
+    pub fn from_pem(public_key_pem: &str) -> Result<TrustedKeys, JwtError> {
+        PublicKey::from_pem(public_key_pem).map(TrustedKeys::new)
+    }
+
+    // Overwrites any existing key with the same `kid`. A public key is only held once, so adding
+    // one which is already trusted under a different `kid` moves it to the new `kid`.
+    pub fn add(&mut self, kid: Option<String>, public_key: PublicKey) {
+        self.keys
+            .retain(|key| key.public_key != public_key && (kid.is_none() || key.kid != kid));
+        self.keys.push(TrustedKey { kid, public_key });
+    }
+
+    // Removes the key whose `kid` or PEM matches `key`. Returns false if there was no such key.
+    pub fn retire(&mut self, key: &str) -> bool {
+        let count = self.keys.len();
+        let public_key = PublicKey::from_pem(key).ok();
+
+        self.keys.retain(|k| {
+            k.kid.as_deref() != Some(key) && public_key.as_ref() != Some(&k.public_key)
+        });
+
+        self.keys.len() < count
+    }
+
+    pub fn keys(&self) -> &[TrustedKey] {
+        &self.keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl From<PublicKey> for TrustedKeys {
+    fn from(public_key: PublicKey) -> Self {
+        TrustedKeys::new(public_key)
+    }
+}
+
+impl JwtVerifier for TrustedKeys {
+    fn verify_claims<T: DeserializeOwned>(
+        &self,
+        jwt: &str,
+        claim_type: &str,
+        now: TimestampMillis,
+        leeway: Milliseconds,
+    ) -> Result<T, JwtError> {
+        let kid = jwt::decode_header(jwt)?.kid;
+
+        // Fall back to trying every key if none has the token's `kid`, since keys can be added
+        // without one
+        let matching: Vec<_> = self
+            .keys
+            .iter()
+            .filter(|key| kid.is_some() && key.kid == kid)
+            .collect();
+        let candidates = if matching.is_empty() {
+            self.keys.iter().collect()
+        } else {
+            matching
+        };
+
+        let mut result = Err(JwtError::InvalidKey("No trusted keys".to_string()));
+
+        for key in candidates {
+            result = key.public_key.verify_claims(jwt, claim_type, now, leeway);
+
+            // Any other error means the token was signed by this key, or is invalid whichever key
+            // is used, so there is no point trying the rest
+            if !matches!(result, Err(JwtError::InvalidSignature)) {
+                break;
+            }
+        }
+
+        result
+    }
+}
+
+impl Serialize for TrustedKeys {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.keys.serialize(serializer)
+    }
+}
+
+// Bots previously held a single key, so a lone PEM is still accepted as well as a list of keys
+impl<'de> Deserialize<'de> for TrustedKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Format {
+            Single(String),
+            Multiple(Vec<TrustedKey>),
+        }
+
+        Ok(match Format::deserialize(deserializer)? {
+            Format::Single(public_key_pem) => {
+                TrustedKeys::from_pem(&public_key_pem).map_err(serde::de::Error::custom)?
+            }
+            Format::Multiple(keys) => TrustedKeys { keys },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwt::Claims;
+    use p256::ecdsa::SigningKey;
+    use p256::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+
+    const CLAIM_TYPE: &str = "BotActionByCommand";
+    const NOW: TimestampMillis = 1_700_000_000_000;
+
+    fn keys() -> (String, String) {
+        let signing_key = SigningKey::random(&mut rand::thread_rng());
+        let private_key_pem = signing_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .unwrap()
+            .to_string();
+        let public_key_pem = signing_key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        (private_key_pem, public_key_pem)
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Custom {
+        value: u32,
+    }
+
+    fn claims() -> Claims<Custom> {
+        Claims::new(NOW + 60_000, CLAIM_TYPE.to_string(), Custom { value: 1 })
+    }
+
+    fn verify(trusted_keys: &TrustedKeys, jwt: &str) -> Result<Custom, JwtError> {
+        trusted_keys.verify_claims(jwt, CLAIM_TYPE, NOW, 0)
+    }
+
+    fn public_key(public_key_pem: &str) -> PublicKey {
+        PublicKey::from_pem(public_key_pem).unwrap()
+    }
+
+    #[test]
+    fn keys_can_be_rotated() {
+        let (old_private_key, old_public_key) = keys();
+        let (new_private_key, new_public_key) = keys();
+        let old_jwt = jwt::sign(&claims(), &old_private_key).unwrap();
+        let new_jwt = jwt::sign(&claims(), &new_private_key).unwrap();
+
+        let mut trusted_keys = TrustedKeys::from_pem(&old_public_key).unwrap();
+        assert!(verify(&trusted_keys, &old_jwt).is_ok());
+        assert_eq!(
+            verify(&trusted_keys, &new_jwt).err(),
+            Some(JwtError::InvalidSignature)
+        );
+
+        trusted_keys.add(None, public_key(&new_public_key));
+        assert!(verify(&trusted_keys, &old_jwt).is_ok());
+        assert!(verify(&trusted_keys, &new_jwt).is_ok());
+
+        assert!(trusted_keys.retire(&old_public_key));
+        assert!(!trusted_keys.retire(&old_public_key));
+        assert_eq!(
+            verify(&trusted_keys, &old_jwt).err(),
+            Some(JwtError::InvalidSignature)
+        );
+        assert!(verify(&trusted_keys, &new_jwt).is_ok());
+        assert_eq!(trusted_keys.len(), 1);
+    }
+
+    #[test]
+    fn keys_are_matched_by_kid() {
+        let (private_key, public_key_pem) = keys();
+        let (_, other_public_key_pem) = keys();
+        let jwt = jwt::sign_with_kid(&claims(), &private_key, "2".to_string()).unwrap();
+
+        let mut trusted_keys = TrustedKeys::default();
+        trusted_keys.add(Some("1".to_string()), public_key(&public_key_pem));
+        trusted_keys.add(Some("2".to_string()), public_key(&other_public_key_pem));
+
+        // Only the key named by the token is tried
+        assert_eq!(
+            verify(&trusted_keys, &jwt).err(),
+            Some(JwtError::InvalidSignature)
+        );
+
+        // Adding a key with an existing kid replaces it, and since the key is already trusted under
+        // kid "1" it is moved from there
+        trusted_keys.add(Some("2".to_string()), public_key(&public_key_pem));
+        let kids: Vec<_> = trusted_keys
+            .keys()
+            .iter()
+            .map(|key| key.kid.as_deref())
+            .collect();
+        assert_eq!(kids, [Some("2")]);
+        assert!(verify(&trusted_keys, &jwt).is_ok());
+
+        assert!(trusted_keys.retire("2"));
+        assert!(verify(&trusted_keys, &jwt).is_err());
+    }
+
+    #[test]
+    fn deserializes_from_a_single_key() {
+        let (_, public_key_pem) = keys();
+
+        let trusted_keys: TrustedKeys =
+            serde_json::from_str(&serde_json::to_string(&public_key_pem).unwrap()).unwrap();
+        assert_eq!(
+            trusted_keys,
+            TrustedKeys::from_pem(&public_key_pem).unwrap()
+        );
+
+        let json = serde_json::to_string(&trusted_keys).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TrustedKeys>(&json).unwrap(),
+            trusted_keys
+        );
+
+        assert!(serde_json::from_str::<TrustedKeys>(r#""not-a-key""#).is_err());
+    }
+}
@@ -12,13 +12,7 @@ pub type Nanoseconds = u64;
 pub type TimestampMillis = u64;
 pub type TimestampNanos = u64;
 
-pub type CallResult<T> = Result<T, CallError>;
-pub type CallError = (i32, String);
-
-#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
-pub struct OCError(u16, Option<String>);
-
-#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[derive(CandidType, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct UserId(CanisterId);
 
 impl Display for UserId {
@@ -96,3 +90,25 @@ pub enum ActionScope {
     Chat(Chat),
     Community(CanisterId),
 }
+
+// Used for partial updates of optional fields, distinguishing "leave unchanged" from "clear"
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OptionUpdate<T> {
+    #[default]
+    NoChange,
+    SetToNone,
+    SetToSome(T),
+}
+
+impl<T> OptionUpdate<T> {
+    pub fn from_update(value: Option<T>) -> Self {
+        match value {
+            None => OptionUpdate::SetToNone,
+            Some(value) => OptionUpdate::SetToSome(value),
+        }
+    }
+
+    pub fn is_no_change(&self) -> bool {
+        matches!(self, OptionUpdate::NoChange)
+    }
+}
@@ -1,10 +1,11 @@
 use crate::api::command::Command;
-use crate::jwt;
-use crate::jwt::Claims;
+use crate::jwt::{self, JwtError, JwtVerifier};
 use crate::types::{
     ActionContext, ActionScope, AuthToken, BotActionByApiKeyClaims, BotActionByCommandClaims,
-    BotApiKeyToken, BotCommandScope, BotPermissions, CanisterId, ChannelId, Chat, MessageId,
-    MessageIndex, TimestampMillis, TokenError, UserId,
+    BotActionByEventClaims, BotApiKeyToken, BotCommandScope, BotPermissions, CanisterId, ChannelId,
+    Chat, ChatEvent, EventWrapper, MessageId, MessageIndex, Milliseconds, TimestampMillis,
+    TokenError, UserId, BOT_ACTION_BY_API_KEY_CLAIM_TYPE, BOT_ACTION_BY_COMMAND_CLAIM_TYPE,
+    BOT_ACTION_BY_EVENT_CLAIM_TYPE,
 };
 use crate::utils::base64;
 
@@ -19,15 +20,26 @@ pub struct BotCommandContext {
 }
 
 impl BotCommandContext {
-    pub fn parse(jwt: String, public_key: &str, now: TimestampMillis) -> Result<Self, TokenError> {
-        let claims = jwt::verify::<Claims<BotActionByCommandClaims>>(&jwt, public_key)
-            .map_err(|error| TokenError::Invalid(error.to_string()))?;
-
-        if claims.exp_ms() <= now {
-            return Err(TokenError::Expired);
-        }
+    pub fn parse(
+        jwt: String,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+    ) -> Result<Self, TokenError> {
+        BotCommandContext::parse_with_leeway(jwt, public_key, now, jwt::DEFAULT_LEEWAY)
+    }
 
-        let claims = claims.into_custom();
+    pub fn parse_with_leeway(
+        jwt: String,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+        leeway: Milliseconds,
+    ) -> Result<Self, TokenError> {
+        let claims = public_key.verify_claims::<BotActionByCommandClaims>(
+            &jwt,
+            BOT_ACTION_BY_COMMAND_CLAIM_TYPE,
+            now,
+            leeway,
+        )?;
 
         Ok(BotCommandContext {
             token: AuthToken::Jwt(jwt),
@@ -90,7 +102,7 @@ pub struct BotApiKeyContext {
 impl BotApiKeyContext {
     pub fn parse(
         auth_token: AuthToken,
-        public_key: &str,
+        public_key: &(impl JwtVerifier + ?Sized),
         now: TimestampMillis,
     ) -> Result<Self, TokenError> {
         match auth_token {
@@ -101,17 +113,24 @@ impl BotApiKeyContext {
 
     pub fn parse_jwt(
         jwt: String,
-        public_key: &str,
+        public_key: &(impl JwtVerifier + ?Sized),
         now: TimestampMillis,
     ) -> Result<Self, TokenError> {
-        let claims = jwt::verify::<Claims<BotActionByApiKeyClaims>>(&jwt, public_key)
-            .map_err(|error| TokenError::Invalid(error.to_string()))?;
-
-        if claims.exp_ms() <= now {
-            return Err(TokenError::Expired);
-        }
+        BotApiKeyContext::parse_jwt_with_leeway(jwt, public_key, now, jwt::DEFAULT_LEEWAY)
+    }
 
-        let claims = claims.into_custom();
+    pub fn parse_jwt_with_leeway(
+        jwt: String,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+        leeway: Milliseconds,
+    ) -> Result<Self, TokenError> {
+        let claims = public_key.verify_claims::<BotActionByApiKeyClaims>(
+            &jwt,
+            BOT_ACTION_BY_API_KEY_CLAIM_TYPE,
+            now,
+            leeway,
+        )?;
 
         Ok(BotApiKeyContext {
             token: AuthToken::Jwt(jwt),
@@ -123,8 +142,8 @@ impl BotApiKeyContext {
     }
 
     pub fn parse_api_key(api_key: String) -> Result<Self, TokenError> {
-        let extracted: BotApiKeyToken =
-            base64::to_value(&api_key).map_err(|error| TokenError::Invalid(error.to_string()))?;
+        let extracted: BotApiKeyToken = base64::to_value(&api_key)
+            .map_err(|error| TokenError::Invalid(JwtError::Malformed(error.to_string())))?;
 
         Ok(BotApiKeyContext {
             token: AuthToken::ApiKey(api_key),
@@ -173,6 +192,81 @@ impl ActionContext for BotApiKeyContext {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct BotEventContext {
+    pub token: AuthToken,
+    pub bot_id: UserId,
+    pub api_gateway: CanisterId,
+    pub scope: ActionScope,
+    pub thread: Option<MessageIndex>,
+    pub granted_permissions: BotPermissions,
+    pub event: EventWrapper<ChatEvent>,
+}
+
+impl BotEventContext {
+    pub fn parse(
+        jwt: String,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+    ) -> Result<Self, TokenError> {
+        BotEventContext::parse_with_leeway(jwt, public_key, now, jwt::DEFAULT_LEEWAY)
+    }
+
+    pub fn parse_with_leeway(
+        jwt: String,
+        public_key: &(impl JwtVerifier + ?Sized),
+        now: TimestampMillis,
+        leeway: Milliseconds,
+    ) -> Result<Self, TokenError> {
+        let claims = public_key.verify_claims::<BotActionByEventClaims>(
+            &jwt,
+            BOT_ACTION_BY_EVENT_CLAIM_TYPE,
+            now,
+            leeway,
+        )?;
+
+        Ok(BotEventContext {
+            token: AuthToken::Jwt(jwt),
+            bot_id: claims.bot,
+            api_gateway: claims.bot_api_gateway,
+            scope: claims.scope,
+            thread: claims.thread,
+            granted_permissions: claims.granted_permissions,
+            event: claims.event,
+        })
+    }
+}
+
+impl ActionContext for BotEventContext {
+    fn bot_id(&self) -> UserId {
+        self.bot_id
+    }
+
+    fn api_gateway(&self) -> CanisterId {
+        self.api_gateway
+    }
+
+    fn scope(&self) -> ActionScope {
+        self.scope
+    }
+
+    fn granted_permissions(&self) -> Option<&BotPermissions> {
+        Some(&self.granted_permissions)
+    }
+
+    fn message_id(&self) -> Option<MessageId> {
+        None
+    }
+
+    fn thread(&self) -> Option<MessageIndex> {
+        self.thread
+    }
+
+    fn auth_token(&self) -> &AuthToken {
+        &self.token
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use candid::Principal;
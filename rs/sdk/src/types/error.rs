@@ -0,0 +1,406 @@
+use super::BotPermissions;
+use candid::CandidType;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+pub type CallResult<T> = Result<T, OcApiError>;
+
+// The IC reject code for transient system errors, eg. the destination's queue being full
+const SYS_TRANSIENT_REJECT_CODE: i32 = 2;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcApiError {
+    // The request could not be sent or no response was received, eg. due to a network error
+    Transport(String),
+    // The IC rejected the call, `code` being the IC reject code
+    Rejected { code: i32, message: String },
+    // The response could not be decoded
+    Decode(String),
+    // Gave up waiting for the response
+    Timeout,
+    // The action wasn't attempted because the bot hasn't been granted these permissions
+    MissingPermissions(BotPermissions),
+    // The remaining variants are the common failure responses returned by OpenChat
+    FailedAuthentication(String),
+    InvalidRequest(String),
+    NotAuthorized,
+    Frozen,
+    NotFound,
+    C2CError(i32, String),
+    InternalError(String),
+    OCError(OCError),
+    // The failure responses specific to one or more actions
+    MessageNotFound,
+    ThreadNotFound,
+    ChannelNotFound,
+    UserNotInChat,
+    MessageAlreadyFinalised,
+    NameTaken,
+    CannotChangeRole,
+    CannotRemoveUser,
+    DirectChatUnsupported,
+    UserLimitReached(u32),
+    TooManyInvites(u32),
+    // A failure response which this version of the SDK doesn't recognise
+    Other(String),
+}
+
+impl Display for OcApiError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcApiError::Transport(message) => write!(f, "Transport error: {message}"),
+            OcApiError::Rejected { code, message } => {
+                write!(f, "Call rejected: {code}: {message}")
+            }
+            OcApiError::Decode(message) => write!(f, "Failed to decode response: {message}"),
+            OcApiError::Timeout => write!(f, "Timed out waiting for response"),
+            OcApiError::MissingPermissions(permissions) => {
+                write!(
+                    f,
+                    "The bot has not been granted the required permissions: {permissions:?}"
+                )
+            }
+            OcApiError::FailedAuthentication(message) => {
+                write!(f, "Failed authentication: {message}")
+            }
+            OcApiError::InvalidRequest(message) => write!(f, "Invalid request: {message}"),
+            OcApiError::NotAuthorized => write!(f, "Not authorized"),
+            OcApiError::Frozen => write!(f, "Chat frozen"),
+            OcApiError::NotFound => write!(f, "Not found"),
+            OcApiError::C2CError(code, message) => write!(f, "C2C error: {code}: {message}"),
+            OcApiError::InternalError(message) => write!(f, "Internal error: {message}"),
+            OcApiError::OCError(error) => write!(f, "OpenChat error: {error}"),
+            OcApiError::MessageNotFound => write!(f, "Message not found"),
+            OcApiError::ThreadNotFound => write!(f, "Thread not found"),
+            OcApiError::ChannelNotFound => write!(f, "Channel not found"),
+            OcApiError::UserNotInChat => write!(f, "User not in chat"),
+            OcApiError::MessageAlreadyFinalised => write!(f, "Message already finalised"),
+            OcApiError::NameTaken => write!(f, "Name taken"),
+            OcApiError::CannotChangeRole => write!(f, "Cannot change role"),
+            OcApiError::CannotRemoveUser => write!(f, "Cannot remove user"),
+            OcApiError::DirectChatUnsupported => write!(f, "Not supported for direct chats"),
+            OcApiError::UserLimitReached(limit) => write!(f, "User limit reached: {limit}"),
+            OcApiError::TooManyInvites(limit) => write!(f, "Too many invites: {limit}"),
+            OcApiError::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Error for OcApiError {}
+
+impl OcApiError {
+    // True if the call failed in a way which may succeed if it is made again
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OcApiError::Transport(_) | OcApiError::Timeout => true,
+            OcApiError::Rejected { code, .. } => *code == SYS_TRANSIENT_REJECT_CODE,
+            OcApiError::C2CError(..) | OcApiError::InternalError(_) => true,
+            OcApiError::OCError(error) => !error.kind().is_permanent(),
+            _ => false,
+        }
+    }
+
+    // True if retrying the action will not succeed, eg. because the bot has been removed from the
+    // chat or the chat no longer exists
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            OcApiError::Decode(_)
+            | OcApiError::InvalidRequest(_)
+            | OcApiError::MissingPermissions(_)
+            | OcApiError::FailedAuthentication(_)
+            | OcApiError::NotAuthorized
+            | OcApiError::Frozen
+            | OcApiError::NotFound
+            | OcApiError::MessageNotFound
+            | OcApiError::ThreadNotFound
+            | OcApiError::ChannelNotFound
+            | OcApiError::UserNotInChat
+            | OcApiError::MessageAlreadyFinalised
+            | OcApiError::NameTaken
+            | OcApiError::CannotChangeRole
+            | OcApiError::CannotRemoveUser
+            | OcApiError::DirectChatUnsupported
+            | OcApiError::UserLimitReached(_)
+            | OcApiError::TooManyInvites(_) => true,
+            OcApiError::OCError(error) => error.kind().is_permanent(),
+            _ => false,
+        }
+    }
+}
+
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub struct OCError(u16, Option<String>);
+
+impl OCError {
+    pub fn new(code: OCErrorCode, message: Option<String>) -> Self {
+        OCError(code.into(), message)
+    }
+
+    pub fn code(&self) -> u16 {
+        self.0
+    }
+
+    pub fn kind(&self) -> OCErrorCode {
+        self.0.into()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.1.as_deref()
+    }
+}
+
+impl Display for OCError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.1 {
+            Some(message) => write!(f, "{} ({}): {}", self.kind(), self.0, message),
+            None => write!(f, "{} ({})", self.kind(), self.0),
+        }
+    }
+}
+
+impl Error for OCError {}
+
+// The error codes returned by OpenChat. Codes which this version of the SDK doesn't know about are
+// returned as `Unknown`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum OCErrorCode {
+    // The initiator is not permitted to perform the action
+    InitiatorNotFound,
+    InitiatorNotAuthorized,
+    InitiatorSuspended,
+    InitiatorNotInChat,
+    InitiatorNotInCommunity,
+    InitiatorLapsed,
+    InitiatorBlocked,
+    BotNotAuthenticated,
+    BotNotInstalled,
+    // The target of the action could not be found
+    ChatNotFound,
+    CommunityNotFound,
+    ChannelNotFound,
+    ThreadNotFound,
+    MessageNotFound,
+    TargetUserNotFound,
+    TargetUserNotInChat,
+    TargetUserNotInCommunity,
+    // The chat is not in a state which allows the action
+    ChatFrozen,
+    CommunityFrozen,
+    MessageAlreadyFinalised,
+    NoChange,
+    // The request is invalid
+    InvalidRequest,
+    TextTooLong,
+    NameTaken,
+    LimitExceeded,
+    // The request failed within OpenChat and may succeed if retried
+    InternalError,
+    C2CError,
+    Unknown(u16),
+}
+
+impl OCErrorCode {
+    // True if retrying with the same request will not succeed. Unknown codes are assumed to be
+    // transient so that they are retried rather than, say, a reminder being deleted.
+    pub fn is_permanent(&self) -> bool {
+        match self {
+            OCErrorCode::ChatFrozen
+            | OCErrorCode::CommunityFrozen
+            | OCErrorCode::MessageAlreadyFinalised
+            | OCErrorCode::NoChange
+            | OCErrorCode::InvalidRequest
+            | OCErrorCode::TextTooLong
+            | OCErrorCode::NameTaken
+            | OCErrorCode::LimitExceeded => true,
+            OCErrorCode::InternalError | OCErrorCode::C2CError | OCErrorCode::Unknown(_) => false,
+            code => code.is_not_authorized() || code.is_not_found(),
+        }
+    }
+
+    pub fn is_not_authorized(&self) -> bool {
+        matches!(
+            self,
+            OCErrorCode::InitiatorNotFound
+                | OCErrorCode::InitiatorNotAuthorized
+                | OCErrorCode::InitiatorSuspended
+                | OCErrorCode::InitiatorNotInChat
+                | OCErrorCode::InitiatorNotInCommunity
+                | OCErrorCode::InitiatorLapsed
+                | OCErrorCode::InitiatorBlocked
+                | OCErrorCode::BotNotAuthenticated
+                | OCErrorCode::BotNotInstalled
+        )
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            OCErrorCode::ChatNotFound
+                | OCErrorCode::CommunityNotFound
+                | OCErrorCode::ChannelNotFound
+                | OCErrorCode::ThreadNotFound
+                | OCErrorCode::MessageNotFound
+                | OCErrorCode::TargetUserNotFound
+                | OCErrorCode::TargetUserNotInChat
+                | OCErrorCode::TargetUserNotInCommunity
+        )
+    }
+}
+
+// The codes must match the discriminants of `OCErrorCode` in the OpenChat backend
+// (backend/libraries/types/src/error.rs in github.com/open-chat-labs/open-chat), since a code which
+// is mapped to the wrong variant is then retried, or given up on, wrongly
+impl From<u16> for OCErrorCode {
+    fn from(value: u16) -> Self {
+        match value {
+            100 => OCErrorCode::InitiatorNotFound,
+            101 => OCErrorCode::InitiatorNotAuthorized,
+            102 => OCErrorCode::InitiatorSuspended,
+            103 => OCErrorCode::InitiatorNotInChat,
+            104 => OCErrorCode::InitiatorNotInCommunity,
+            105 => OCErrorCode::InitiatorLapsed,
+            106 => OCErrorCode::InitiatorBlocked,
+            107 => OCErrorCode::BotNotAuthenticated,
+            108 => OCErrorCode::BotNotInstalled,
+            200 => OCErrorCode::ChatNotFound,
+            201 => OCErrorCode::CommunityNotFound,
+            202 => OCErrorCode::ChannelNotFound,
+            203 => OCErrorCode::ThreadNotFound,
+            204 => OCErrorCode::MessageNotFound,
+            205 => OCErrorCode::TargetUserNotFound,
+            206 => OCErrorCode::TargetUserNotInChat,
+            207 => OCErrorCode::TargetUserNotInCommunity,
+            300 => OCErrorCode::ChatFrozen,
+            301 => OCErrorCode::CommunityFrozen,
+            302 => OCErrorCode::MessageAlreadyFinalised,
+            303 => OCErrorCode::NoChange,
+            400 => OCErrorCode::InvalidRequest,
+            401 => OCErrorCode::TextTooLong,
+            402 => OCErrorCode::NameTaken,
+            403 => OCErrorCode::LimitExceeded,
+            500 => OCErrorCode::InternalError,
+            501 => OCErrorCode::C2CError,
+            code => OCErrorCode::Unknown(code),
+        }
+    }
+}
+
+impl From<OCErrorCode> for u16 {
+    fn from(value: OCErrorCode) -> Self {
+        match value {
+            OCErrorCode::InitiatorNotFound => 100,
+            OCErrorCode::InitiatorNotAuthorized => 101,
+            OCErrorCode::InitiatorSuspended => 102,
+            OCErrorCode::InitiatorNotInChat => 103,
+            OCErrorCode::InitiatorNotInCommunity => 104,
+            OCErrorCode::InitiatorLapsed => 105,
+            OCErrorCode::InitiatorBlocked => 106,
+            OCErrorCode::BotNotAuthenticated => 107,
+            OCErrorCode::BotNotInstalled => 108,
+            OCErrorCode::ChatNotFound => 200,
+            OCErrorCode::CommunityNotFound => 201,
+            OCErrorCode::ChannelNotFound => 202,
+            OCErrorCode::ThreadNotFound => 203,
+            OCErrorCode::MessageNotFound => 204,
+            OCErrorCode::TargetUserNotFound => 205,
+            OCErrorCode::TargetUserNotInChat => 206,
+            OCErrorCode::TargetUserNotInCommunity => 207,
+            OCErrorCode::ChatFrozen => 300,
+            OCErrorCode::CommunityFrozen => 301,
+            OCErrorCode::MessageAlreadyFinalised => 302,
+            OCErrorCode::NoChange => 303,
+            OCErrorCode::InvalidRequest => 400,
+            OCErrorCode::TextTooLong => 401,
+            OCErrorCode::NameTaken => 402,
+            OCErrorCode::LimitExceeded => 403,
+            OCErrorCode::InternalError => 500,
+            OCErrorCode::C2CError => 501,
+            OCErrorCode::Unknown(code) => code,
+        }
+    }
+}
+
+impl Display for OCErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OCErrorCode::Unknown(code) => write!(f, "Unknown error code {code}"),
+            code => Debug::fmt(code, f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN_CODES: [(OCErrorCode, u16); 27] = [
+        (OCErrorCode::InitiatorNotFound, 100),
+        (OCErrorCode::InitiatorNotAuthorized, 101),
+        (OCErrorCode::InitiatorSuspended, 102),
+        (OCErrorCode::InitiatorNotInChat, 103),
+        (OCErrorCode::InitiatorNotInCommunity, 104),
+        (OCErrorCode::InitiatorLapsed, 105),
+        (OCErrorCode::InitiatorBlocked, 106),
+        (OCErrorCode::BotNotAuthenticated, 107),
+        (OCErrorCode::BotNotInstalled, 108),
+        (OCErrorCode::ChatNotFound, 200),
+        (OCErrorCode::CommunityNotFound, 201),
+        (OCErrorCode::ChannelNotFound, 202),
+        (OCErrorCode::ThreadNotFound, 203),
+        (OCErrorCode::MessageNotFound, 204),
+        (OCErrorCode::TargetUserNotFound, 205),
+        (OCErrorCode::TargetUserNotInChat, 206),
+        (OCErrorCode::TargetUserNotInCommunity, 207),
+        (OCErrorCode::ChatFrozen, 300),
+        (OCErrorCode::CommunityFrozen, 301),
+        (OCErrorCode::MessageAlreadyFinalised, 302),
+        (OCErrorCode::NoChange, 303),
+        (OCErrorCode::InvalidRequest, 400),
+        (OCErrorCode::TextTooLong, 401),
+        (OCErrorCode::NameTaken, 402),
+        (OCErrorCode::LimitExceeded, 403),
+        (OCErrorCode::InternalError, 500),
+        (OCErrorCode::C2CError, 501),
+    ];
+
+    #[test]
+    fn known_codes_round_trip() {
+        for (kind, code) in KNOWN_CODES {
+            assert_eq!(u16::from(kind), code);
+            assert_eq!(OCErrorCode::from(code), kind);
+            assert_eq!(OCError::new(kind, None).kind(), kind);
+        }
+
+        assert_eq!(OCErrorCode::from(999), OCErrorCode::Unknown(999));
+    }
+
+    #[test]
+    fn error_codes_round_trip() {
+        for code in 0..1000u16 {
+            assert_eq!(u16::from(OCErrorCode::from(code)), code);
+        }
+    }
+
+    #[test]
+    fn bot_removed_from_chat_is_permanent() {
+        let removed = OcApiError::OCError(OCError::new(OCErrorCode::InitiatorNotInChat, None));
+        let c2c_failure = OcApiError::OCError(OCError::new(OCErrorCode::C2CError, None));
+
+        assert!(removed.is_permanent());
+        assert!(!c2c_failure.is_permanent());
+        assert!(!OcApiError::Timeout.is_permanent());
+    }
+
+    #[test]
+    fn invalid_requests_are_permanent() {
+        let invalid = OcApiError::OCError(OCError::new(OCErrorCode::InvalidRequest, None));
+        let unknown = OcApiError::OCError(OCError::new(OCErrorCode::Unknown(999), None));
+
+        assert!(invalid.is_permanent());
+        assert!(OcApiError::InvalidRequest("Invalid args".to_string()).is_permanent());
+        assert!(OcApiError::Decode("Unexpected type".to_string()).is_permanent());
+        assert!(!unknown.is_permanent());
+        assert!(unknown.is_retryable());
+    }
+}
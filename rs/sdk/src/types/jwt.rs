@@ -1,5 +1,9 @@
-use super::{ActionScope, BotPermissions, CanisterId, Chat, MessageId, MessageIndex, UserId};
+use super::{
+    ActionScope, BotPermissions, CanisterId, Chat, ChatEvent, EventWrapper, MessageId,
+    MessageIndex, UserId,
+};
 use crate::api::command::Command;
+use crate::jwt::JwtError;
 use candid::CandidType;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -7,15 +11,37 @@ use std::fmt::Display;
 
 #[derive(Debug)]
 pub enum TokenError {
-    Invalid(String),
+    Invalid(JwtError),
     Expired,
 }
 
-impl Error for TokenError {}
+// The `claim_type` of each kind of token issued by OpenChat
+pub const BOT_ACTION_BY_COMMAND_CLAIM_TYPE: &str = "BotActionByCommand";
+pub const BOT_ACTION_BY_API_KEY_CLAIM_TYPE: &str = "BotActionByApiKey";
+pub const BOT_ACTION_BY_EVENT_CLAIM_TYPE: &str = "BotActionByEvent";
+
+impl From<JwtError> for TokenError {
+    fn from(error: JwtError) -> Self {
+        match error {
+            JwtError::Expired => TokenError::Expired,
+            error => TokenError::Invalid(error),
+        }
+    }
+}
+
+impl Error for TokenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TokenError::Invalid(error) => Some(error),
+            TokenError::Expired => None,
+        }
+    }
+}
+
 impl Display for TokenError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            TokenError::Invalid(msg) => write!(f, "Invalid token: {}", msg),
+            TokenError::Invalid(error) => write!(f, "Invalid token: {}", error),
             TokenError::Expired => write!(f, "Token has expired"),
         }
     }
@@ -38,6 +64,16 @@ pub struct BotActionByApiKeyClaims {
     pub granted_permissions: BotPermissions,
 }
 
+#[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
+pub struct BotActionByEventClaims {
+    pub bot_api_gateway: CanisterId,
+    pub bot: UserId,
+    pub scope: ActionScope,
+    pub thread: Option<MessageIndex>,
+    pub granted_permissions: BotPermissions,
+    pub event: EventWrapper<ChatEvent>,
+}
+
 #[derive(CandidType, Serialize, Deserialize, Clone, Debug)]
 pub enum BotCommandScope {
     Chat(BotActionChatDetails),
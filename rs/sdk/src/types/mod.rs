@@ -3,6 +3,7 @@ mod api_key;
 mod basic;
 mod bot_context;
 mod chat;
+mod error;
 mod events;
 mod jwt;
 mod message_content;
@@ -14,6 +15,7 @@ pub use api_key::*;
 pub use basic::*;
 pub use bot_context::*;
 pub use chat::*;
+pub use error::*;
 pub use events::*;
 pub use jwt::*;
 pub use message_content::*;
@@ -1,12 +1,76 @@
-use ct_codecs::{Base64UrlSafeNoPadding, Decoder};
+use ct_codecs::{Base64UrlSafeNoPadding, Decoder, Encoder};
 use p256::ecdsa;
-use p256::ecdsa::signature::Verifier;
-use p256::pkcs8::DecodePublicKey;
+use p256::ecdsa::signature::{Signer, Verifier};
+use p256::pkcs8::{DecodePrivateKey, DecodePublicKey};
 use serde::de::DeserializeOwned;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-use crate::types::TimestampMillis;
+use crate::types::{Milliseconds, TimestampMillis};
+
+// The only algorithm OpenChat signs tokens with
+const ALGORITHM: &str = "ES256";
+const TOKEN_TYPE: &str = "JWT";
+
+// Tokens are rejected as soon as they expire unless a leeway is passed to one of the
+// `parse_with_leeway` functions, eg. to allow for the clocks of OpenChat and the bot being slightly
+// out of sync
+pub const DEFAULT_LEEWAY: Milliseconds = 0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JwtError {
+    Malformed(String),
+    UnsupportedAlgorithm(String),
+    UnsupportedType(String),
+    InvalidKey(String),
+    InvalidSignature,
+    InvalidClaims(String),
+    UnexpectedClaimType { expected: String, actual: String },
+    Expired,
+}
+
+impl Error for JwtError {}
+
+impl Display for JwtError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JwtError::Malformed(msg) => write!(f, "Malformed jwt: {msg}"),
+            JwtError::UnsupportedAlgorithm(alg) => write!(f, "Unsupported algorithm: {alg}"),
+            JwtError::UnsupportedType(typ) => write!(f, "Unsupported token type: {typ}"),
+            JwtError::InvalidKey(msg) => write!(f, "Invalid key: {msg}"),
+            JwtError::InvalidSignature => write!(f, "Invalid signature"),
+            JwtError::InvalidClaims(msg) => write!(f, "Invalid claims: {msg}"),
+            JwtError::UnexpectedClaimType { expected, actual } => {
+                write!(
+                    f,
+                    "Unexpected claim type: expected {expected}, got {actual}"
+                )
+            }
+            JwtError::Expired => write!(f, "Token has expired"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Header {
+    pub alg: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+}
+
+impl Default for Header {
+    fn default() -> Self {
+        Header {
+            alg: ALGORITHM.to_string(),
+            typ: Some(TOKEN_TYPE.to_string()),
+            kid: None,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct Claims<T> {
@@ -46,26 +110,326 @@ impl<T> Claims<T> {
     }
 }
 
-pub fn verify<T: DeserializeOwned>(jwt: &str, public_key_pem: &str) -> Result<T, Box<dyn Error>> {
-    let mut parts = jwt.split('.');
-    let header_json = parts.next().ok_or("Invalid jwt")?;
-    let claims_json = parts.next().ok_or("Invalid jwt")?;
-    let signature_str = parts.next().ok_or("Invalid jwt")?;
+/// A P-256 public key, parsed from its PEM.
+///
+/// Parsing the PEM costs far more than verifying a token's signature, which canister bots pay for
+/// in instructions, so the key should be parsed once, eg. when the bot is initialized, and then
+/// used to verify every token. Serializes as the PEM.
+#[derive(Clone, Debug)]
+pub struct PublicKey {
+    pem: String,
+    verifying_key: ecdsa::VerifyingKey,
+}
+
+impl PublicKey {
+    pub fn from_pem(pem: &str) -> Result<PublicKey, JwtError> {
+        let verifying_key = ecdsa::VerifyingKey::from_public_key_pem(pem)
+            .map_err(|error| JwtError::InvalidKey(error.to_string()))?;
+
+        Ok(PublicKey {
+            pem: pem.to_string(),
+            verifying_key,
+        })
+    }
+
+    pub fn pem(&self) -> &str {
+        &self.pem
+    }
+}
+
+// The same key may be encoded in different ways so only the key itself is compared
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.verifying_key == other.verifying_key
+    }
+}
+
+impl Eq for PublicKey {}
+
+impl FromStr for PublicKey {
+    type Err = JwtError;
+
+    fn from_str(pem: &str) -> Result<Self, Self::Err> {
+        PublicKey::from_pem(pem)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.pem.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let pem = String::deserialize(deserializer)?;
+        PublicKey::from_pem(&pem).map_err(serde::de::Error::custom)
+    }
+}
+
+// Implemented by anything which can verify the tokens OpenChat sends to bots, ie. a single parsed
+// `PublicKey` or a set of `TrustedKeys`. A PEM must be parsed into a `PublicKey` up front rather
+// than being parsed again for every token.
+pub trait JwtVerifier {
+    fn verify_claims<T: DeserializeOwned>(
+        &self,
+        jwt: &str,
+        claim_type: &str,
+        now: TimestampMillis,
+        leeway: Milliseconds,
+    ) -> Result<T, JwtError>;
+}
+
+impl JwtVerifier for PublicKey {
+    fn verify_claims<T: DeserializeOwned>(
+        &self,
+        jwt: &str,
+        claim_type: &str,
+        now: TimestampMillis,
+        leeway: Milliseconds,
+    ) -> Result<T, JwtError> {
+        let claims: Claims<T> = verify_with_key(jwt, self)?;
+
+        if claims.claim_type != claim_type {
+            return Err(JwtError::UnexpectedClaimType {
+                expected: claim_type.to_string(),
+                actual: claims.claim_type,
+            });
+        }
+
+        if claims.exp_ms().saturating_add(leeway) <= now {
+            return Err(JwtError::Expired);
+        }
+
+        Ok(claims.custom)
+    }
+}
+
+// Verifies the signature and checks that the claims are of the expected type and haven't expired
+pub fn verify_claims<T: DeserializeOwned>(
+    jwt: &str,
+    public_key_pem: &str,
+    claim_type: &str,
+    now: TimestampMillis,
+    leeway: Milliseconds,
+) -> Result<T, JwtError> {
+    PublicKey::from_pem(public_key_pem)?.verify_claims(jwt, claim_type, now, leeway)
+}
+
+pub fn verify<T: DeserializeOwned>(jwt: &str, public_key_pem: &str) -> Result<T, JwtError> {
+    verify_with_key(jwt, &PublicKey::from_pem(public_key_pem)?)
+}
+
+pub fn verify_with_key<T: DeserializeOwned>(
+    jwt: &str,
+    public_key: &PublicKey,
+) -> Result<T, JwtError> {
+    let [header_json, claims_json, signature_str] = split(jwt)?;
+
+    let header: Header = decode_from_json(header_json)?;
+    validate_header(&header)?;
+
     let signature_bytes = decode_to_bytes(signature_str)?;
-    let signature = ecdsa::Signature::from_slice(&signature_bytes)?;
+    let signature =
+        ecdsa::Signature::from_slice(&signature_bytes).map_err(|_| JwtError::InvalidSignature)?;
     let authenticated = format!("{header_json}.{claims_json}");
 
-    let verifying_key = ecdsa::VerifyingKey::from_public_key_pem(public_key_pem)?;
-    verifying_key.verify(authenticated.as_bytes(), &signature)?;
+    public_key
+        .verifying_key
+        .verify(authenticated.as_bytes(), &signature)
+        .map_err(|_| JwtError::InvalidSignature)?;
+
+    let bytes = decode_to_bytes(claims_json)?;
+    serde_json::from_slice(&bytes).map_err(|error| JwtError::InvalidClaims(error.to_string()))
+}
+
+// Decodes the header without verifying the signature, eg. to find which key (`kid`) to verify with
+pub fn decode_header(jwt: &str) -> Result<Header, JwtError> {
+    let [header_json, _, _] = split(jwt)?;
+    decode_from_json(header_json)
+}
+
+// Signs the claims with ES256, the inverse of `verify`. OpenChat signs the tokens it sends to bots
+// so this is only needed to produce tokens for tests and local development.
+pub fn sign<T: Serialize>(claims: &T, private_key_pem: &str) -> Result<String, JwtError> {
+    sign_with_header(&Header::default(), claims, private_key_pem)
+}
+
+// As `sign` but includes the id of the signing key in the header so that the bot knows which of
+// its trusted keys to verify the token with
+pub fn sign_with_kid<T: Serialize>(
+    claims: &T,
+    private_key_pem: &str,
+    kid: String,
+) -> Result<String, JwtError> {
+    let header = Header {
+        kid: Some(kid),
+        ..Header::default()
+    };
+
+    sign_with_header(&header, claims, private_key_pem)
+}
+
+fn sign_with_header<T: Serialize>(
+    header: &Header,
+    claims: &T,
+    private_key_pem: &str,
+) -> Result<String, JwtError> {
+    let signing_key = ecdsa::SigningKey::from_pkcs8_pem(private_key_pem)
+        .map_err(|error| JwtError::InvalidKey(error.to_string()))?;
+
+    let header_json = encode_to_json(header)?;
+    let claims_json = encode_to_json(claims)?;
+    let authenticated = format!("{header_json}.{claims_json}");
+
+    let signature: ecdsa::Signature = signing_key.sign(authenticated.as_bytes());
+    let signature_str = encode_to_string(&signature.to_bytes())?;
+
+    Ok(format!("{authenticated}.{signature_str}"))
+}
+
+fn validate_header(header: &Header) -> Result<(), JwtError> {
+    if header.alg != ALGORITHM {
+        return Err(JwtError::UnsupportedAlgorithm(header.alg.clone()));
+    }
+
+    if let Some(typ) = header.typ.as_ref().filter(|typ| *typ != TOKEN_TYPE) {
+        return Err(JwtError::UnsupportedType(typ.clone()));
+    }
+
+    Ok(())
+}
+
+fn split(jwt: &str) -> Result<[&str; 3], JwtError> {
+    let parts: Vec<_> = jwt.split('.').collect();
 
-    decode_from_json(claims_json)
+    parts.try_into().map_err(|parts: Vec<_>| {
+        JwtError::Malformed(format!("Expected 3 parts, got {}", parts.len()))
+    })
 }
 
-fn decode_from_json<T: DeserializeOwned>(s: &str) -> Result<T, Box<dyn Error>> {
+fn encode_to_json<T: Serialize>(value: &T) -> Result<String, JwtError> {
+    let bytes =
+        serde_json::to_vec(value).map_err(|error| JwtError::Malformed(error.to_string()))?;
+    encode_to_string(&bytes)
+}
+
+fn encode_to_string(bytes: &[u8]) -> Result<String, JwtError> {
+    Base64UrlSafeNoPadding::encode_to_string(bytes)
+        .map_err(|error| JwtError::Malformed(error.to_string()))
+}
+
+fn decode_from_json<T: DeserializeOwned>(s: &str) -> Result<T, JwtError> {
     let bytes = decode_to_bytes(s)?;
-    Ok(serde_json::from_slice(&bytes)?)
+    serde_json::from_slice(&bytes).map_err(|error| JwtError::Malformed(error.to_string()))
 }
 
-fn decode_to_bytes(s: &str) -> Result<Vec<u8>, ct_codecs::Error> {
+fn decode_to_bytes(s: &str) -> Result<Vec<u8>, JwtError> {
     Base64UrlSafeNoPadding::decode_to_vec(s, None)
+        .map_err(|error| JwtError::Malformed(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::pkcs8::{EncodePrivateKey, EncodePublicKey, LineEnding};
+
+    const CLAIM_TYPE: &str = "BotActionByCommand";
+    const NOW: TimestampMillis = 1_700_000_000_000;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Custom {
+        value: u32,
+    }
+
+    fn keys() -> (String, String) {
+        let signing_key = ecdsa::SigningKey::random(&mut rand::thread_rng());
+        let private_key_pem = signing_key
+            .to_pkcs8_pem(LineEnding::LF)
+            .unwrap()
+            .to_string();
+        let public_key_pem = signing_key
+            .verifying_key()
+            .to_public_key_pem(LineEnding::LF)
+            .unwrap();
+        (private_key_pem, public_key_pem)
+    }
+
+    #[test]
+    fn claims_are_checked() {
+        let (private_key_pem, public_key_pem) = keys();
+        let jwt = sign(
+            &Claims::new(NOW, CLAIM_TYPE.to_string(), Custom { value: 1 }),
+            &private_key_pem,
+        )
+        .unwrap();
+
+        // Expired but within the leeway
+        assert!(
+            verify_claims::<Custom>(&jwt, &public_key_pem, CLAIM_TYPE, NOW + 1_000, 5_000).is_ok()
+        );
+
+        assert_eq!(
+            verify_claims::<Custom>(&jwt, &public_key_pem, CLAIM_TYPE, NOW + 1_000, 0),
+            Err(JwtError::Expired)
+        );
+        assert!(matches!(
+            verify_claims::<Custom>(&jwt, &public_key_pem, "BotActionByApiKey", NOW, 0),
+            Err(JwtError::UnexpectedClaimType { .. })
+        ));
+    }
+
+    #[test]
+    fn header_is_checked() {
+        let (private_key_pem, public_key_pem) = keys();
+        let jwt = sign(
+            &Claims::new(NOW, CLAIM_TYPE.to_string(), Custom { value: 1 }),
+            &private_key_pem,
+        )
+        .unwrap();
+        let (_, rest) = jwt.split_once('.').unwrap();
+
+        let header = Header {
+            alg: "none".to_string(),
+            typ: None,
+            kid: None,
+        };
+        let tampered = format!("{}.{rest}", encode_to_json(&header).unwrap());
+
+        assert_eq!(
+            verify::<Claims<Custom>>(&tampered, &public_key_pem).err(),
+            Some(JwtError::UnsupportedAlgorithm("none".to_string()))
+        );
+        assert!(matches!(
+            verify::<Claims<Custom>>(rest, &public_key_pem),
+            Err(JwtError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn parsed_key_verifies_tokens() {
+        let (private_key_pem, public_key_pem) = keys();
+        let jwt = sign(
+            &Claims::new(NOW, CLAIM_TYPE.to_string(), Custom { value: 1 }),
+            &private_key_pem,
+        )
+        .unwrap();
+
+        let public_key = PublicKey::from_pem(&public_key_pem).unwrap();
+        assert_eq!(
+            public_key.verify_claims::<Custom>(&jwt, CLAIM_TYPE, NOW - 1_000, 0),
+            Ok(Custom { value: 1 })
+        );
+
+        let json = serde_json::to_string(&public_key).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PublicKey>(&json).unwrap(),
+            public_key
+        );
+
+        assert!(matches!(
+            PublicKey::from_pem("not-a-key"),
+            Err(JwtError::InvalidKey(_))
+        ));
+    }
 }